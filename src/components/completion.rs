@@ -0,0 +1,95 @@
+/// How the argument being completed is delimited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Argument {
+    /// Words separated by spaces, like the terms of a query.
+    Words,
+    /// The whole remaining text, like a directory which may contain spaces.
+    Rest,
+    /// Values separated by commas.
+    List,
+}
+
+/// Result of a completion: the new text, and the candidates when several remain.
+#[derive(Debug, Default)]
+pub(crate) struct Completion {
+    pub(crate) text: String,
+    pub(crate) candidates: Vec<String>,
+}
+
+/// Completes the end of the text with the candidates starting with it, up to their longest
+/// common prefix.
+pub(crate) fn complete_with(text: &str, argument: Argument, candidates: &[String]) -> Completion {
+    let start = match argument {
+        Argument::Words => text
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map_or(0, |(index, c)| index + c.len_utf8()),
+        Argument::Rest => text.len() - text.trim_start().len(),
+        Argument::List => text.rfind(',').map_or(0, |index| index + 1),
+    };
+    let (head, word) = text.split_at(start);
+    let word = word.trim_start();
+    let head = match argument {
+        Argument::List if !head.is_empty() => format!("{}, ", head.trim_end_matches(',')),
+        _ => head.to_string(),
+    };
+    let mut matches = candidates
+        .iter()
+        .filter(|candidate| candidate.starts_with(word) && candidate.as_str() != word)
+        .cloned()
+        .collect::<Vec<_>>();
+    matches.sort();
+    matches.dedup();
+    match matches.as_slice() {
+        [] => Completion {
+            text: text.to_string(),
+            candidates: Vec::new(),
+        },
+        [single] => {
+            let open = single.ends_with([':', '<', '>', '/']);
+            let separator = match argument {
+                Argument::Words if !open => " ",
+                _ => "",
+            };
+            Completion {
+                text: format!("{head}{single}{separator}"),
+                candidates: Vec::new(),
+            }
+        }
+        [first, rest @ ..] => {
+            let prefix = rest.iter().fold(first.as_str(), |prefix, candidate| {
+                let length = prefix
+                    .char_indices()
+                    .zip(candidate.chars())
+                    .find(|((_, left), right)| left != right)
+                    .map_or(prefix.len().min(candidate.len()), |((index, _), _)| index);
+                &prefix[..length]
+            });
+            Completion {
+                text: format!("{head}{prefix}"),
+                candidates: matches,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Argument, complete_with};
+
+    #[test]
+    fn complete_with_splits_on_multibyte_whitespace() {
+        let candidates = vec!["label:linux".to_string()];
+        let completion = complete_with("name:a\u{3000}lab", Argument::Words, &candidates);
+        assert_eq!(completion.text, "name:a\u{3000}label:linux ");
+    }
+
+    #[test]
+    fn complete_with_stops_at_the_common_prefix() {
+        let candidates = vec!["/data/movies".to_string(), "/data/music".to_string()];
+        let completion = complete_with("/da", Argument::Rest, &candidates);
+        assert_eq!(completion.text, "/data/m");
+        assert_eq!(completion.candidates, candidates);
+    }
+}
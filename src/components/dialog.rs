@@ -0,0 +1,586 @@
+use crossterm::event::{Event, KeyCode, KeyEvent, MouseButton, MouseEventKind};
+use ratatui::layout::{Constraint, Flex, Layout, Position};
+use ratatui::prelude::{Buffer, Rect};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::block::Title;
+use ratatui::widgets::{Block, Clear, Padding, Paragraph, Widget};
+
+use crate::components::input::TextInput;
+use crate::components::subtitle::{Subtitle, SubtitleItem};
+use crate::keymap::{Chord, Command, KeyPress, Keymap, Resolution, Scope};
+
+/// Minimum width of the values, so that the text inputs have some room.
+const VALUE_MIN_WIDTH: usize = 30;
+
+/// Text input only accepting numbers, parsed with their unit like `2M` or `500kB`.
+#[derive(Debug)]
+pub(crate) struct NumberInput {
+    input: TextInput,
+    unit: &'static str,
+    parse: fn(&str) -> Option<f64>,
+}
+
+impl NumberInput {
+    pub fn new(input: TextInput, unit: &'static str, parse: fn(&str) -> Option<f64>) -> Self {
+        Self { input, unit, parse }
+    }
+
+    /// Parsed value, `None` when empty.
+    pub fn value(&self) -> Result<Option<f64>, ()> {
+        let value = self.input.value().trim();
+        if value.is_empty() {
+            return Ok(None);
+        }
+        (self.parse)(value).map(Some).ok_or(())
+    }
+}
+
+/// Choice between a few options, cycled with the left and right keys.
+#[derive(Debug)]
+pub(crate) struct Select {
+    options: Vec<&'static str>,
+    selected: usize,
+}
+
+impl Select {
+    pub fn new(options: Vec<&'static str>, selected: usize) -> Self {
+        Self { options, selected }
+    }
+
+    fn cycle(&mut self, forward: bool) {
+        let len = self.options.len().max(1);
+        self.selected = if forward {
+            (self.selected + 1) % len
+        } else {
+            (self.selected + len - 1) % len
+        };
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum FieldKind {
+    Text(TextInput),
+    Number(NumberInput),
+    Checkbox(bool),
+    Select(Select),
+}
+
+impl FieldKind {
+    /// Edits the field with the key, returning false when the key isn't handled.
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match self {
+            Self::Text(input) => input.handle_key(key),
+            Self::Number(number) => number.input.handle_key(key),
+            Self::Checkbox(checked) => {
+                let toggle = matches!(key.code, KeyCode::Char(' '));
+                if toggle {
+                    *checked = !*checked;
+                }
+                toggle
+            }
+            Self::Select(select) => match key.code {
+                KeyCode::Left => {
+                    select.cycle(false);
+                    true
+                }
+                KeyCode::Right | KeyCode::Char(' ') => {
+                    select.cycle(true);
+                    true
+                }
+                _ => false,
+            },
+        }
+    }
+
+    fn input_mut(&mut self) -> Option<&mut TextInput> {
+        match self {
+            Self::Text(input) => Some(input),
+            Self::Number(number) => Some(&mut number.input),
+            _ => None,
+        }
+    }
+
+    fn validate(&self) -> Option<&'static str> {
+        match self {
+            Self::Text(input) => input.validate(),
+            Self::Number(number) => number
+                .input
+                .validate()
+                .or_else(|| number.value().err().map(|_| "is not a valid number")),
+            _ => None,
+        }
+    }
+
+    fn line(&self, focused: bool) -> Line<'_> {
+        match self {
+            Self::Text(input) => input.line(focused),
+            Self::Number(number) => {
+                let mut line = number.input.line(focused);
+                line.push_span(format!(" {}", number.unit).fg(Color::Gray));
+                line
+            }
+            Self::Checkbox(checked) => Line::from(if *checked { "[x]" } else { "[ ]" }),
+            Self::Select(select) => {
+                let option = select.options.get(select.selected).copied();
+                Line::from(format!("‹ {} ›", option.unwrap_or_default()))
+            }
+        }
+    }
+
+    fn width(&self) -> usize {
+        match self {
+            Self::Text(input) => input.width(),
+            Self::Number(number) => number.input.width() + number.unit.len() + 1,
+            Self::Checkbox(_) => 3,
+            Self::Select(select) => {
+                select
+                    .options
+                    .iter()
+                    .map(|option| option.len())
+                    .max()
+                    .unwrap_or_default()
+                    + 4
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Field {
+    label: &'static str,
+    kind: FieldKind,
+}
+
+/// Line of the dialog content, to find what is under the mouse.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Row {
+    Message(usize),
+    Blank,
+    Field(usize),
+    Candidates(usize),
+    Error,
+}
+
+/// Centered popup sized to its content, with a message, fields and the options in its bottom
+/// border.
+///
+/// The keys go to the focused field first, then to the dialog bindings. In a form, the keys
+/// editing text never reach the bindings, so that typing on a checkbox doesn't close it.
+#[derive(Debug)]
+pub(crate) struct Dialog {
+    title: String,
+    message: Vec<String>,
+    fields: Vec<Field>,
+    focus: usize,
+    options: Subtitle,
+    chord: Chord,
+    error: Option<String>,
+}
+
+impl Dialog {
+    pub fn new(title: impl Into<String>, options: Subtitle) -> Self {
+        Self {
+            title: title.into(),
+            message: Vec::new(),
+            fields: Vec::new(),
+            focus: 0,
+            options,
+            chord: Chord::default(),
+            error: None,
+        }
+    }
+
+    /// Options of a form, with the first keys closing and submitting it that don't edit text.
+    pub fn form_options(keymap: &Keymap) -> Subtitle {
+        let items = [Command::Close, Command::Select]
+            .into_iter()
+            .filter_map(|command| {
+                let keys = keymap
+                    .keys(Scope::Dialog, command)
+                    .find(|keys| !keys.edits_text())?;
+                Some(SubtitleItem::new(command, keys.hint()))
+            })
+            .collect::<Vec<_>>();
+        Subtitle::from(items)
+    }
+
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = message.into().lines().map(String::from).collect();
+        self
+    }
+
+    pub fn with_field(mut self, label: &'static str, kind: FieldKind) -> Self {
+        self.fields.push(Field { label, kind });
+        self
+    }
+
+    fn field(&self, label: &str) -> Option<&FieldKind> {
+        self.fields
+            .iter()
+            .find(|field| field.label == label)
+            .map(|field| &field.kind)
+    }
+
+    pub fn text(&self, label: &str) -> &str {
+        match self.field(label) {
+            Some(FieldKind::Text(input)) => input.value(),
+            _ => "",
+        }
+    }
+
+    pub fn number(&self, label: &str) -> Option<f64> {
+        match self.field(label) {
+            Some(FieldKind::Number(number)) => number.value().ok().flatten(),
+            _ => None,
+        }
+    }
+
+    pub fn checked(&self, label: &str) -> bool {
+        matches!(self.field(label), Some(FieldKind::Checkbox(true)))
+    }
+
+    pub fn selected(&self, label: &str) -> usize {
+        match self.field(label) {
+            Some(FieldKind::Select(select)) => select.selected,
+            _ => 0,
+        }
+    }
+
+    /// Displays the error and focuses the field it is about.
+    pub fn fail(&mut self, label: &str, error: impl Into<String>) {
+        if let Some(index) = self.fields.iter().position(|field| field.label == label) {
+            self.focus = index;
+        }
+        self.error = Some(error.into());
+    }
+
+    /// Checks every field, failing on the first invalid one.
+    fn validate(&mut self) -> bool {
+        let invalid = self
+            .fields
+            .iter()
+            .find_map(|field| field.kind.validate().map(|error| (field.label, error)));
+        match invalid {
+            Some((label, error)) => {
+                self.fail(label, format!("{label} {error}"));
+                false
+            }
+            None => true,
+        }
+    }
+
+    fn move_focus(&mut self, forward: bool) {
+        let len = self.fields.len().max(1);
+        self.focus = if forward {
+            (self.focus + 1) % len
+        } else {
+            (self.focus + len - 1) % len
+        };
+    }
+
+    fn focused_input(&mut self) -> Option<&mut TextInput> {
+        self.fields
+            .get_mut(self.focus)
+            .and_then(|field| field.kind.input_mut())
+    }
+
+    /// Command chosen by the key, the `Select` command being only returned once the fields are
+    /// valid.
+    fn handle_key(&mut self, keymap: &Keymap, key: KeyEvent) -> Option<Command> {
+        match key.code {
+            KeyCode::Esc => return Some(Command::Close),
+            KeyCode::Enter => return self.submit(),
+            KeyCode::Tab => {
+                if !self.focused_input().is_some_and(TextInput::complete) {
+                    self.move_focus(true);
+                }
+                return None;
+            }
+            KeyCode::BackTab => {
+                self.move_focus(false);
+                return None;
+            }
+            KeyCode::Up
+                if self
+                    .focused_input()
+                    .is_some_and(TextInput::history_previous) =>
+            {
+                return None;
+            }
+            KeyCode::Down if self.focused_input().is_some_and(TextInput::history_next) => {
+                return None;
+            }
+            _ => {}
+        }
+        if let Some(field) = self.fields.get_mut(self.focus)
+            && field.kind.handle_key(key)
+        {
+            self.chord.clear();
+            self.error = None;
+            return None;
+        }
+        if !self.fields.is_empty() && KeyPress::from(key).edits_text() {
+            self.chord.clear();
+            return None;
+        }
+        match self.chord.resolve(keymap, Scope::Dialog, key) {
+            Resolution::Command(Command::Up) => self.move_focus(false),
+            Resolution::Command(Command::Down) => self.move_focus(true),
+            Resolution::Command(Command::Select) => return self.submit(),
+            Resolution::Command(command) => return Some(command),
+            Resolution::Pending | Resolution::Unbound => {}
+        }
+        None
+    }
+
+    fn submit(&mut self) -> Option<Command> {
+        self.validate().then_some(Command::Select)
+    }
+
+    /// Handles the input, returning the command chosen by the user.
+    pub fn update(&mut self, keymap: &Keymap, area: Rect, event: &Event) -> Option<Command> {
+        match event {
+            Event::Key(key) => self.handle_key(keymap, *key),
+            Event::Mouse(mouse) => {
+                let position = Position::new(mouse.column, mouse.row);
+                match mouse.kind {
+                    MouseEventKind::Down(MouseButton::Left) => self.click(area, position),
+                    MouseEventKind::ScrollUp => {
+                        self.move_focus(false);
+                        None
+                    }
+                    MouseEventKind::ScrollDown => {
+                        self.move_focus(true);
+                        None
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn click(&mut self, area: Rect, position: Position) -> Option<Command> {
+        let area = self.area(area);
+        if let Some(command) = self.options.command_at(area, position) {
+            return match command {
+                Command::Select => self.submit(),
+                other => Some(other),
+            };
+        }
+        let inner = self.block().inner(area);
+        if !inner.contains(position) {
+            return None;
+        }
+        let row = self.rows().get(usize::from(position.y - inner.y)).copied();
+        if let Some(Row::Field(index)) = row {
+            self.focus = index;
+            if let Some(field) = self.fields.get_mut(index) {
+                match &mut field.kind {
+                    FieldKind::Checkbox(checked) => *checked = !*checked,
+                    FieldKind::Select(select) => select.cycle(true),
+                    _ => {}
+                }
+            }
+        }
+        None
+    }
+
+    fn rows(&self) -> Vec<Row> {
+        let mut rows = (0..self.message.len())
+            .map(Row::Message)
+            .collect::<Vec<_>>();
+        if !rows.is_empty() && !self.fields.is_empty() {
+            rows.push(Row::Blank);
+        }
+        for (index, field) in self.fields.iter().enumerate() {
+            rows.push(Row::Field(index));
+            let candidates = match &field.kind {
+                FieldKind::Text(input) => input.candidates(),
+                _ => &[],
+            };
+            if index == self.focus && !candidates.is_empty() {
+                rows.push(Row::Candidates(index));
+            }
+        }
+        if self.error.is_some() {
+            rows.push(Row::Blank);
+            rows.push(Row::Error);
+        }
+        rows
+    }
+
+    fn label_width(&self) -> usize {
+        self.fields
+            .iter()
+            .map(|field| field.label.len())
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn block(&self) -> Block<'_> {
+        Block::bordered()
+            .title(Title::from(format!(" {} ", self.title).bold()))
+            .title_bottom(self.options.line())
+            .padding(Padding::symmetric(2, 1))
+    }
+
+    /// Area of the dialog, centered in the given one and sized to fit the content.
+    fn area(&self, area: Rect) -> Rect {
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| field.kind.width().max(VALUE_MIN_WIDTH))
+            .max()
+            .map_or(0, |width| self.label_width() + 2 + width);
+        let content = self
+            .message
+            .iter()
+            .map(|line| line.chars().count())
+            .chain([
+                fields,
+                self.title.len() + 2,
+                self.options.line().width(),
+                self.error.as_ref().map_or(0, |error| error.chars().count()),
+            ])
+            .max()
+            .unwrap_or_default();
+        let width = content as u16 + 6;
+        let height = self.rows().len() as u16 + 4;
+
+        let vertical = Layout::vertical([Constraint::Max(height)]).flex(Flex::Center);
+        let horizontal = Layout::horizontal([Constraint::Max(width)]).flex(Flex::Center);
+        let [area] = vertical.areas(area);
+        let [area] = horizontal.areas(area);
+        area
+    }
+}
+
+impl Widget for &Dialog {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let area = self.area(area);
+        let label_width = self.label_width();
+        let lines = self
+            .rows()
+            .into_iter()
+            .map(|row| match row {
+                Row::Message(index) => Line::from(self.message[index].as_str()),
+                Row::Blank => Line::default(),
+                Row::Field(index) => {
+                    let field = &self.fields[index];
+                    let focused = index == self.focus;
+                    let label = format!("{:<label_width$}  ", field.label);
+                    let label = if focused {
+                        Span::styled(label, Style::new().bold().yellow())
+                    } else {
+                        Span::raw(label)
+                    };
+                    let mut line = field.kind.line(focused);
+                    line.spans.insert(0, label);
+                    line
+                }
+                Row::Candidates(index) => {
+                    let candidates = match &self.fields[index].kind {
+                        FieldKind::Text(input) => input.candidates().join("  "),
+                        _ => String::new(),
+                    };
+                    Line::from(vec![
+                        " ".repeat(label_width + 2).into(),
+                        candidates.fg(Color::Gray),
+                    ])
+                }
+                Row::Error => Line::from(self.error.as_deref().unwrap_or_default()).red(),
+            })
+            .collect::<Vec<_>>();
+
+        Clear.render(area, buf);
+        Paragraph::new(lines).block(self.block()).render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::prelude::Rect;
+
+    use super::{Dialog, FieldKind};
+    use crate::components::input::TextInput;
+    use crate::config::KeymapConfig;
+    use crate::keymap::{Command, Keymap};
+
+    fn keymap() -> Keymap {
+        Keymap::new(&KeymapConfig::default()).expect("the default keymap should be valid")
+    }
+
+    fn press(dialog: &mut Dialog, keymap: &Keymap, code: KeyCode) -> Option<Command> {
+        let event = Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        dialog.update(keymap, Rect::new(0, 0, 80, 24), &event)
+    }
+
+    fn form(keymap: &Keymap) -> Dialog {
+        Dialog::new("Form", Dialog::form_options(keymap))
+            .with_field("Name", FieldKind::Text(TextInput::default().required()))
+            .with_field("Paused", FieldKind::Checkbox(false))
+    }
+
+    #[test]
+    fn focus_cycles_through_the_fields() {
+        let keymap = keymap();
+        let mut dialog = form(&keymap);
+        press(&mut dialog, &keymap, KeyCode::Tab);
+        assert_eq!(dialog.focus, 1);
+        press(&mut dialog, &keymap, KeyCode::Tab);
+        assert_eq!(dialog.focus, 0);
+        press(&mut dialog, &keymap, KeyCode::BackTab);
+        assert_eq!(dialog.focus, 1);
+        press(&mut dialog, &keymap, KeyCode::Down);
+        assert_eq!(dialog.focus, 0);
+    }
+
+    #[test]
+    fn invalid_fields_are_not_submitted() {
+        let keymap = keymap();
+        let mut dialog = form(&keymap);
+        press(&mut dialog, &keymap, KeyCode::Tab);
+        assert_eq!(press(&mut dialog, &keymap, KeyCode::Enter), None);
+        assert_eq!(dialog.focus, 0);
+        assert_eq!(dialog.error.as_deref(), Some("Name is required"));
+        press(&mut dialog, &keymap, KeyCode::Char('a'));
+        assert_eq!(dialog.error, None);
+        assert_eq!(
+            press(&mut dialog, &keymap, KeyCode::Enter),
+            Some(Command::Select)
+        );
+        assert_eq!(dialog.text("Name"), "a");
+    }
+
+    #[test]
+    fn typing_on_a_checkbox_does_not_close_the_form() {
+        let keymap = keymap();
+        let mut dialog = form(&keymap);
+        press(&mut dialog, &keymap, KeyCode::Tab);
+        for code in [KeyCode::Char('c'), KeyCode::Char('m'), KeyCode::Backspace] {
+            assert_eq!(press(&mut dialog, &keymap, code), None);
+        }
+        press(&mut dialog, &keymap, KeyCode::Char(' '));
+        assert!(dialog.checked("Paused"));
+        assert_eq!(
+            press(&mut dialog, &keymap, KeyCode::Esc),
+            Some(Command::Close)
+        );
+    }
+
+    #[test]
+    fn form_options_show_the_keys_not_editing_text() {
+        let keymap = keymap();
+        let options = Dialog::form_options(&keymap);
+        assert_eq!(
+            options.line().to_string(),
+            " Cancel <Esc> - Select <Enter> "
+        );
+    }
+}
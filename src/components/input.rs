@@ -0,0 +1,272 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+
+use crate::components::completion::{Argument, complete_with};
+
+/// Single line text input, with a cursor, a history and a completion.
+#[derive(Debug, Default)]
+pub(crate) struct TextInput {
+    value: String,
+    /// Position of the cursor, in characters.
+    cursor: usize,
+    placeholder: &'static str,
+    required: bool,
+    /// Previous values, the most recent last, browsed with the up and down keys.
+    history: Vec<String>,
+    history_index: Option<usize>,
+    /// Text being typed before browsing the history, restored when leaving it.
+    draft: String,
+    completions: Vec<String>,
+    argument: Option<Argument>,
+    /// Completions left after the last tab, when several match.
+    candidates: Vec<String>,
+}
+
+impl TextInput {
+    pub fn new(value: impl Into<String>) -> Self {
+        let value = value.into();
+        Self {
+            cursor: value.chars().count(),
+            value,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_placeholder(mut self, placeholder: &'static str) -> Self {
+        self.placeholder = placeholder;
+        self
+    }
+
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    pub fn with_history(mut self, history: Vec<String>) -> Self {
+        self.history = history;
+        self
+    }
+
+    /// Values offered with the tab key, the argument telling which part of the text is completed.
+    pub fn with_completions(mut self, completions: Vec<String>, argument: Argument) -> Self {
+        self.completions = completions;
+        self.argument = Some(argument);
+        self
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.value = value.into();
+        self.cursor = self.value.chars().count();
+        self.candidates.clear();
+    }
+
+    pub fn candidates(&self) -> &[String] {
+        &self.candidates
+    }
+
+    /// Error to display when the value is not acceptable.
+    pub fn validate(&self) -> Option<&'static str> {
+        (self.required && self.value.trim().is_empty()).then_some("is required")
+    }
+
+    /// Byte offset of the cursor in the value.
+    fn offset(&self) -> usize {
+        self.value
+            .char_indices()
+            .nth(self.cursor)
+            .map_or(self.value.len(), |(index, _)| index)
+    }
+
+    fn edited(&mut self) {
+        self.history_index = None;
+        self.candidates.clear();
+    }
+
+    fn insert(&mut self, c: char) {
+        let offset = self.offset();
+        self.value.insert(offset, c);
+        self.cursor += 1;
+        self.edited();
+    }
+
+    fn remove_before(&mut self) {
+        if self.cursor == 0 {
+            return;
+        }
+        self.cursor -= 1;
+        let offset = self.offset();
+        self.value.remove(offset);
+        self.edited();
+    }
+
+    fn remove_after(&mut self) {
+        let offset = self.offset();
+        if offset < self.value.len() {
+            self.value.remove(offset);
+            self.edited();
+        }
+    }
+
+    /// Removes the word before the cursor, like the shells do with `C-w`.
+    fn remove_word(&mut self) {
+        let offset = self.offset();
+        let head = self.value[..offset].trim_end();
+        let start = head.rfind(char::is_whitespace).map_or(0, |index| index + 1);
+        self.value.replace_range(start..offset, "");
+        self.cursor = self.value[..start].chars().count();
+        self.edited();
+    }
+
+    /// Completes the value, returning false when there was nothing to complete.
+    pub fn complete(&mut self) -> bool {
+        let Some(argument) = self.argument else {
+            return false;
+        };
+        let completion = complete_with(&self.value, argument, &self.completions);
+        let changed = completion.text != self.value || !completion.candidates.is_empty();
+        self.value = completion.text;
+        self.cursor = self.value.chars().count();
+        self.candidates = completion.candidates;
+        changed
+    }
+
+    /// Shows an older value of the history, returning false when there is no history.
+    pub fn history_previous(&mut self) -> bool {
+        if self.history.is_empty() {
+            return false;
+        }
+        let index = match self.history_index {
+            None => {
+                self.draft = self.value.clone();
+                self.history.len() - 1
+            }
+            Some(index) => index.saturating_sub(1),
+        };
+        self.history_index = Some(index);
+        self.set_value(self.history[index].clone());
+        true
+    }
+
+    /// Shows a more recent value of the history, or the draft after the most recent one,
+    /// returning false when the history isn't being browsed.
+    pub fn history_next(&mut self) -> bool {
+        let Some(index) = self.history_index else {
+            return false;
+        };
+        if index + 1 < self.history.len() {
+            self.history_index = Some(index + 1);
+            self.set_value(self.history[index + 1].clone());
+        } else {
+            self.history_index = None;
+            let draft = std::mem::take(&mut self.draft);
+            self.set_value(draft);
+        }
+        true
+    }
+
+    /// Edits the value with the key, returning false when the key isn't handled.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('u') if control => {
+                self.value.clear();
+                self.cursor = 0;
+                self.edited();
+            }
+            KeyCode::Char('w') if control => self.remove_word(),
+            KeyCode::Char('a') if control => self.cursor = 0,
+            KeyCode::Char('e') if control => self.cursor = self.value.chars().count(),
+            KeyCode::Char(_) if control => return false,
+            KeyCode::Char(c) => self.insert(c),
+            KeyCode::Backspace => self.remove_before(),
+            KeyCode::Delete => self.remove_after(),
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(self.value.chars().count()),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.value.chars().count(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Value with the cursor when focused, or the placeholder when empty.
+    pub fn line(&self, focused: bool) -> Line<'_> {
+        if self.value.is_empty() && !focused {
+            return Line::from(self.placeholder.dim());
+        }
+        if !focused {
+            return Line::from(self.value.as_str());
+        }
+        let offset = self.offset();
+        let (head, tail) = self.value.split_at(offset);
+        let mut chars = tail.chars();
+        let under = chars.next().map_or(" ".to_string(), String::from);
+        Line::from(vec![
+            Span::raw(head),
+            Span::styled(under, Style::new().reversed()),
+            Span::raw(chars.as_str()),
+        ])
+    }
+
+    pub fn width(&self) -> usize {
+        self.value.chars().count().max(self.placeholder.len()) + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::TextInput;
+
+    fn press(input: &mut TextInput, code: KeyCode) -> bool {
+        input.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn keys_edit_at_the_cursor() {
+        let mut input = TextInput::new("héllo");
+        press(&mut input, KeyCode::Left);
+        press(&mut input, KeyCode::Left);
+        press(&mut input, KeyCode::Char('x'));
+        assert_eq!(input.value(), "hélxlo");
+        press(&mut input, KeyCode::Home);
+        press(&mut input, KeyCode::Delete);
+        press(&mut input, KeyCode::Right);
+        press(&mut input, KeyCode::Backspace);
+        assert_eq!(input.value(), "lxlo");
+        press(&mut input, KeyCode::End);
+        input.handle_key(KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL));
+        assert_eq!(input.value(), "");
+    }
+
+    #[test]
+    fn history_is_browsed_and_restores_the_draft() {
+        let history = vec!["first".to_string(), "second".to_string()];
+        let mut input = TextInput::new("draft").with_history(history);
+        assert!(!input.history_next());
+        assert!(input.history_previous());
+        assert_eq!(input.value(), "second");
+        assert!(input.history_previous());
+        assert!(input.history_previous());
+        assert_eq!(input.value(), "first");
+        assert!(input.history_next());
+        assert_eq!(input.value(), "second");
+        assert!(input.history_next());
+        assert_eq!(input.value(), "draft");
+        assert!(!input.history_next());
+    }
+
+    #[test]
+    fn empty_history_is_not_browsed() {
+        let mut input = TextInput::new("draft");
+        assert!(!input.history_previous());
+        assert!(!input.history_next());
+        assert_eq!(input.value(), "draft");
+    }
+}
@@ -1,7 +1,9 @@
-use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::layout::{Constraint, Direction, Layout, Position};
 use ratatui::prelude::{Buffer, Rect};
 use ratatui::style::{Style, Stylize};
-use ratatui::widgets::{Block, Widget};
+use ratatui::widgets::{
+    Block, Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget, Widget,
+};
 
 pub trait ListItem: Widget {
     fn height(&self) -> u16;
@@ -44,67 +46,178 @@ where
     }
 }
 
-#[derive(Default)]
-pub struct List<'a, I> {
-    items: &'a [I],
+/// Selection and scroll position of a [`List`], kept between two renders.
+#[derive(Debug, Default)]
+pub struct ListState {
     offset: usize,
     selected: Option<usize>,
+    /// Number of items that fit in the viewport during the last render.
+    page_size: usize,
+    /// Viewport and heights of the visible items during the last render, to find the item under
+    /// the mouse.
+    area: Rect,
+    heights: Vec<u16>,
 }
 
-impl<'a, I> List<'a, I> {
-    pub fn new(items: &'a [I], offset: usize, selected: Option<usize>) -> Self {
-        Self {
-            items,
-            offset,
-            selected,
-        }
+impl ListState {
+    pub fn offset(&self) -> usize {
+        self.offset
     }
 
-    pub fn set_items(&mut self, items: &'a [I]) {
-        self.items = items;
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
     }
 
-    pub fn with_items(mut self, items: &'a [I]) -> Self {
-        self.set_items(items);
-        self
+    pub fn select(&mut self, selected: Option<usize>) {
+        self.selected = selected;
     }
 
-    pub fn set_offset(&mut self, offset: usize) {
-        self.offset = offset;
+    pub fn select_previous(&mut self, len: usize) {
+        self.move_selection(len, |current| current.saturating_sub(1));
     }
 
-    pub fn with_offset(mut self, offset: usize) -> Self {
-        self.offset = offset;
-        self
+    pub fn select_next(&mut self, len: usize) {
+        self.move_selection(len, |current| current + 1);
     }
 
-    pub fn set_selected(&mut self, selected: Option<usize>) {
-        self.selected = selected;
+    pub fn select_previous_page(&mut self, len: usize) {
+        let page = self.page_size.max(1);
+        self.move_selection(len, |current| current.saturating_sub(page));
+    }
+
+    pub fn select_next_page(&mut self, len: usize) {
+        let page = self.page_size.max(1);
+        self.move_selection(len, |current| current + page);
+    }
+
+    pub fn select_first(&mut self, len: usize) {
+        self.move_selection(len, |_| 0);
     }
 
-    pub fn with_selected(mut self, selected: Option<usize>) -> Self {
-        self.set_selected(selected);
+    pub fn select_last(&mut self, len: usize) {
+        self.move_selection(len, |_| usize::MAX);
+    }
+
+    /// Index of the item under the position, from the offset and the heights of the visible items.
+    pub fn index_at(&self, position: Position) -> Option<usize> {
+        if !self.area.contains(position) {
+            return None;
+        }
+        let mut bottom = self.area.y;
+        self.heights
+            .iter()
+            .position(|height| {
+                bottom += height;
+                position.y < bottom
+            })
+            .map(|index| self.offset + index)
+    }
+
+    /// Whether the position is in the viewport of the list.
+    pub fn contains(&self, position: Position) -> bool {
+        self.area.contains(position)
+    }
+
+    /// Applies the move to the current selection, or selects the first item when nothing is
+    /// selected yet.
+    fn move_selection(&mut self, len: usize, apply: impl FnOnce(usize) -> usize) {
+        self.selected = if len == 0 {
+            None
+        } else if let Some(current) = self.selected {
+            Some(apply(current).min(len - 1))
+        } else {
+            Some(0)
+        };
+    }
+}
+
+pub struct List<'a, I> {
+    items: &'a [I],
+}
+
+impl<'a, I> List<'a, I> {
+    pub fn new(items: &'a [I]) -> Self {
+        Self { items }
+    }
+
+    pub fn set_items(&mut self, items: &'a [I]) {
+        self.items = items;
+    }
+
+    pub fn with_items(mut self, items: &'a [I]) -> Self {
+        self.set_items(items);
         self
     }
 }
 
-impl<'a, I> Widget for &'a List<'a, I>
+impl<'a, I> List<'a, I>
 where
     &'a I: ListItem + 'a,
 {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        let available_height = area.height;
-        let mut constraints = Vec::default();
+    /// Number of items that fit in the given height, starting at the given offset.
+    fn fitting(&self, offset: usize, height: u16) -> usize {
         let mut total = 0u16;
-        for item in self.items.iter().skip(self.offset) {
-            let h = item.height();
-            if total + h <= available_height {
-                constraints.push(Constraint::Length(item.height()));
-                total += h;
-            } else {
-                break;
-            }
+        self.items
+            .iter()
+            .skip(offset)
+            .take_while(|item| {
+                total = total.saturating_add(item.height());
+                total <= height
+            })
+            .count()
+    }
+
+    /// Moves the offset so that the selected item is fully visible.
+    fn scroll_to_selected(&self, state: &mut ListState, height: u16) {
+        state.offset = state.offset.min(self.items.len().saturating_sub(1));
+        let Some(selected) = state.selected.filter(|index| *index < self.items.len()) else {
+            return;
+        };
+        if selected < state.offset {
+            state.offset = selected;
+        }
+        while state.offset < selected
+            && state.offset + self.fitting(state.offset, height) <= selected
+        {
+            state.offset += 1;
         }
+    }
+}
+
+impl<'a, I> StatefulWidget for &'a List<'a, I>
+where
+    &'a I: ListItem + 'a,
+{
+    type State = ListState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut ListState) {
+        let total_height = self
+            .items
+            .iter()
+            .fold(0u16, |acc, item| acc.saturating_add(item.height()));
+        let overflowing = total_height > area.height;
+        let [area, scrollbar] = Layout::horizontal([
+            Constraint::Min(0),
+            Constraint::Length(u16::from(overflowing)),
+        ])
+        .areas(area);
+
+        self.scroll_to_selected(state, area.height);
+        state.page_size = self.fitting(state.offset, area.height);
+
+        state.area = area;
+        state.heights = self
+            .items
+            .iter()
+            .skip(state.offset)
+            .take(state.page_size)
+            .map(|item| item.height())
+            .collect();
+        let constraints = state
+            .heights
+            .iter()
+            .map(|height| Constraint::Length(*height))
+            .collect::<Vec<_>>();
         let layouts = Layout::default()
             .direction(Direction::Vertical)
             .constraints(constraints)
@@ -112,11 +225,63 @@ where
         self.items
             .iter()
             .enumerate()
-            .skip(self.offset)
+            .skip(state.offset)
             .zip(&*layouts)
             .for_each(|((index, item), area)| {
-                let selected = self.selected.map(|i| i == index).unwrap_or(false);
+                let selected = state.selected.map(|i| i == index).unwrap_or(false);
                 ItemWrapper::new(item, selected).render(*area, buf);
             });
+
+        if overflowing {
+            let mut scrollbar_state = ScrollbarState::new(self.items.len())
+                .viewport_content_length(state.page_size)
+                .position(state.selected.unwrap_or(state.offset));
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .render(scrollbar, buf, &mut scrollbar_state);
+        }
+    }
+}
+
+/// Row of a list mixing two kinds of items, like group headers and the items of the groups.
+///
+/// The items are indented to appear under their header.
+#[derive(Clone, Copy, Debug)]
+pub enum Entry<H, I> {
+    Header(H),
+    Item(I),
+}
+
+impl<H, I> Widget for &Entry<H, I>
+where
+    H: Copy + Widget,
+    I: Copy + Widget,
+{
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        match *self {
+            Entry::Header(header) => header.render(area, buf),
+            Entry::Item(item) => {
+                let [_, area] =
+                    Layout::horizontal([Constraint::Length(2), Constraint::Min(0)]).areas(area);
+                item.render(area, buf);
+            }
+        }
+    }
+}
+
+impl<H, I> ListItem for &Entry<H, I>
+where
+    H: Copy + ListItem,
+    I: Copy + ListItem,
+{
+    fn height(&self) -> u16 {
+        match self {
+            Entry::Header(header) => header.height(),
+            Entry::Item(item) => item.height(),
+        }
     }
 }
@@ -0,0 +1,103 @@
+use ratatui::layout::{Constraint, Flex, Layout, Position};
+use ratatui::prelude::{Buffer, Rect};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::block::Title;
+use ratatui::widgets::{Block, Clear, Padding, Paragraph, Widget};
+use unicode_width::UnicodeWidthStr;
+
+use crate::components::subtitle::SubtitleItem;
+
+/// Centered popup listing actions with their shortcut, the selected one being highlighted.
+pub(crate) struct Menu<'a> {
+    title: &'a str,
+    items: &'a [SubtitleItem],
+    selected: usize,
+}
+
+impl<'a> Menu<'a> {
+    pub const fn new(title: &'a str, items: &'a [SubtitleItem], selected: usize) -> Self {
+        Self {
+            title,
+            items,
+            selected,
+        }
+    }
+}
+
+impl Menu<'_> {
+    fn widths(&self) -> (usize, usize) {
+        let label_width = self
+            .items
+            .iter()
+            .map(|item| item.label().width())
+            .max()
+            .unwrap_or_default();
+        let trigger_width = self
+            .items
+            .iter()
+            .map(|item| item.trigger().width() + 2)
+            .max()
+            .unwrap_or_default();
+        (label_width, trigger_width)
+    }
+
+    /// Area of the popup, centered in the given one and sized to fit the entries.
+    fn area(&self, area: Rect) -> Rect {
+        let (label_width, trigger_width) = self.widths();
+        let width = (label_width + trigger_width + 3).max(self.title.width()) as u16 + 4;
+        let height = self.items.len() as u16 + 2;
+
+        let vertical = Layout::vertical([Constraint::Max(height)]).flex(Flex::Center);
+        let horizontal = Layout::horizontal([Constraint::Max(width)]).flex(Flex::Center);
+        let [area] = vertical.areas(area);
+        let [area] = horizontal.areas(area);
+        area
+    }
+
+    /// Index of the entry under the position.
+    pub fn item_at(&self, area: Rect, position: Position) -> Option<usize> {
+        let area = self.area(area);
+        let inner = Rect {
+            x: area.x + 1,
+            y: area.y + 1,
+            width: area.width.saturating_sub(2),
+            height: area.height.saturating_sub(2),
+        };
+        inner
+            .contains(position)
+            .then(|| usize::from(position.y - inner.y))
+            .filter(|index| *index < self.items.len())
+    }
+}
+
+impl Widget for &Menu<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let (label_width, trigger_width) = self.widths();
+        let area = self.area(area);
+
+        let block = Block::bordered()
+            .title(Title::from(self.title.bold()))
+            .padding(Padding::horizontal(1));
+        let lines = self.items.iter().enumerate().map(|(index, item)| {
+            let trigger = format!("<{}>", item.trigger());
+            let line = Line::from(vec![
+                format!(" {:<label_width$} ", item.label()).into(),
+                format!("{trigger:>trigger_width$} ").bold(),
+            ]);
+            if index == self.selected {
+                line.style(Style::new().reversed())
+            } else {
+                line
+            }
+        });
+
+        Clear.render(area, buf);
+        Paragraph::new(lines.collect::<Vec<_>>())
+            .block(block)
+            .render(area, buf);
+    }
+}
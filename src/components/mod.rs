@@ -1,9 +1,14 @@
 use std::sync::LazyLock;
 
-use transmission_rpc::types::TorrentStatus;
+use chrono::{DateTime, Local, Utc};
+use transmission_rpc::types::{Torrent, TorrentStatus};
 
-pub(crate) mod confirm;
+pub(crate) mod completion;
+pub(crate) mod dialog;
+pub(crate) mod input;
 pub(crate) mod list;
+pub(crate) mod menu;
+pub(crate) mod mouse;
 pub(crate) mod subtitle;
 
 pub(crate) static SIZE_FORMATTER: LazyLock<human_number::Formatter<'static>> =
@@ -22,3 +27,162 @@ pub(crate) fn torrent_status_label(status: TorrentStatus) -> &'static str {
         TorrentStatus::Seeding => "Seeding",
     }
 }
+
+pub(crate) fn format_duration(seconds: i64) -> String {
+    let days = seconds / 86_400;
+    let hours = (seconds % 86_400) / 3_600;
+    let minutes = (seconds % 3_600) / 60;
+    let seconds = seconds % 60;
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+pub(crate) fn format_percent(value: f64) -> String {
+    format!("{:.1}%", value * 100.0)
+}
+
+/// Transmission uses `-1` when the ETA is not available and `-2` when it is unknown.
+pub(crate) fn format_eta(eta: i64) -> String {
+    match eta {
+        -1 => "Not available".into(),
+        value if value < 0 => "Unknown".into(),
+        value => format_duration(value),
+    }
+}
+
+/// Transmission reports unset dates as `0`, which gets deserialized as the unix epoch.
+pub(crate) fn format_date(value: Option<DateTime<Utc>>) -> String {
+    match value {
+        Some(value) if value > DateTime::UNIX_EPOCH => value
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string(),
+        _ => "Never".into(),
+    }
+}
+
+/// Parses a size like `10G`, `1.5MB` or `700KiB` into bytes, SI units being powers of 1000 and
+/// binary ones powers of 1024.
+pub(crate) fn parse_size(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number = number.parse::<f64>().ok()?;
+    let unit = unit.trim().to_ascii_lowercase();
+    let unit = unit.strip_suffix("/s").unwrap_or(&unit);
+    let (prefix, base) = if let Some(prefix) = unit.strip_suffix("ib") {
+        (prefix, 1024f64)
+    } else {
+        (unit.strip_suffix('b').unwrap_or(unit), 1000f64)
+    };
+    let exponent = match prefix {
+        "" => 0,
+        "k" => 1,
+        "m" => 2,
+        "g" => 3,
+        "t" => 4,
+        "p" => 5,
+        _ => return None,
+    };
+    Some(number * base.powi(exponent))
+}
+
+/// Parses a speed limit like `2M` or `500kB` into kB/s, a bare number being already in kB/s like
+/// in the transmission settings.
+pub(crate) fn parse_speed(value: &str) -> Option<i32> {
+    let value = value.trim();
+    if value.chars().all(|c| c.is_ascii_digit()) {
+        return value.parse().ok();
+    }
+    parse_size(value).map(|bytes| (bytes / 1000.0).round() as i32)
+}
+
+/// Parses a duration like `30s`, `15m`, `12h`, `7d` or `2w` into seconds.
+pub(crate) fn parse_duration(value: &str) -> Option<i64> {
+    let value = value.trim();
+    let (index, _) = value.char_indices().last()?;
+    let (number, unit) = value.split_at(index);
+    let number = number.parse::<i64>().ok()?;
+    let factor = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3_600,
+        "d" => 86_400,
+        "w" => 604_800,
+        _ => return None,
+    };
+    number.checked_mul(factor)
+}
+
+/// Hosts of the trackers of the torrent, in the order of their tiers.
+pub(crate) fn tracker_hosts(torrent: &Torrent) -> impl Iterator<Item = String> + '_ {
+    let mut trackers = torrent.trackers.iter().flatten().collect::<Vec<_>>();
+    trackers.sort_by_key(|tracker| tracker.tier);
+    trackers.into_iter().filter_map(|tracker| {
+        url::Url::parse(&tracker.announce)
+            .ok()
+            .and_then(|url| url.host_str().map(String::from))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_duration, parse_size, parse_speed};
+
+    #[test]
+    fn parse_size_supports_the_si_and_binary_units() {
+        assert_eq!(parse_size("512"), Some(512.0));
+        assert_eq!(parse_size("10G"), Some(10e9));
+        assert_eq!(parse_size("1.5MB"), Some(1.5e6));
+        assert_eq!(parse_size(" 700 KiB "), Some(700.0 * 1024.0));
+        assert_eq!(parse_size("2tb"), Some(2e12));
+        assert_eq!(parse_size("1MB/s"), Some(1e6));
+    }
+
+    #[test]
+    fn parse_size_rejects_the_invalid_values() {
+        assert_eq!(parse_size(""), None);
+        assert_eq!(parse_size("G"), None);
+        assert_eq!(parse_size("10X"), None);
+        assert_eq!(parse_size("1.2.3M"), None);
+        assert_eq!(parse_size("10é"), None);
+    }
+
+    #[test]
+    fn parse_speed_reads_the_bare_numbers_in_kilobytes() {
+        assert_eq!(parse_speed("500"), Some(500));
+        assert_eq!(parse_speed("2M"), Some(2000));
+        assert_eq!(parse_speed("500kB"), Some(500));
+        assert_eq!(parse_speed("1500B"), Some(2));
+        assert_eq!(parse_speed("fast"), None);
+    }
+
+    #[test]
+    fn parse_duration_supports_the_units() {
+        assert_eq!(parse_duration("30s"), Some(30));
+        assert_eq!(parse_duration("15m"), Some(900));
+        assert_eq!(parse_duration("12h"), Some(43_200));
+        assert_eq!(parse_duration(" 7d "), Some(604_800));
+        assert_eq!(parse_duration("2w"), Some(1_209_600));
+    }
+
+    #[test]
+    fn parse_duration_rejects_the_invalid_values() {
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("d"), None);
+        assert_eq!(parse_duration("7"), None);
+        assert_eq!(parse_duration("7y"), None);
+        assert_eq!(parse_duration("7é"), None);
+        assert_eq!(parse_duration("éd"), None);
+        assert_eq!(parse_duration("99999999999999999d"), None);
+    }
+}
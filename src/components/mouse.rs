@@ -0,0 +1,74 @@
+use std::time::{Duration, Instant};
+
+use ratatui::layout::{Position, Rect};
+use unicode_width::UnicodeWidthStr;
+
+/// Maximum delay between the two clicks of a double-click.
+const DOUBLE_CLICK_DELAY: Duration = Duration::from_millis(400);
+
+/// Index of the tab under the position, for [`ratatui::widgets::Tabs`] rendered with the default
+/// padding and divider.
+pub(crate) fn tab_at<'a>(
+    labels: impl IntoIterator<Item = &'a str>,
+    area: Rect,
+    position: Position,
+) -> Option<usize> {
+    if !area.contains(position) {
+        return None;
+    }
+    let mut x = area.x;
+    for (index, label) in labels.into_iter().enumerate() {
+        // one space of padding on each side, then the divider
+        let width = label.width() as u16 + 2;
+        if position.x < x + width {
+            return Some(index);
+        }
+        x += width + 1;
+    }
+    None
+}
+
+/// Remembers the last click, to detect double-clicks on the same target.
+#[derive(Debug, Default)]
+pub(crate) struct ClickTracker {
+    last: Option<(Instant, usize)>,
+}
+
+impl ClickTracker {
+    /// Records a click on the target, and returns whether it completes a double-click.
+    pub(crate) fn click(&mut self, target: usize) -> bool {
+        let now = Instant::now();
+        let double = self.last.is_some_and(|(at, previous)| {
+            previous == target && now.duration_since(at) <= DOUBLE_CLICK_DELAY
+        });
+        self.last = if double { None } else { Some((now, target)) };
+        double
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::{Position, Rect};
+
+    use super::tab_at;
+
+    #[test]
+    fn tabs_are_found_by_their_column() {
+        let area = Rect::new(0, 0, 40, 1);
+        let labels = ["1 Overview", "2 Files"];
+        // " 1 Overview " spans 0..12, the divider 12, then " 2 Files " spans 13..22
+        assert_eq!(tab_at(labels, area, Position::new(11, 0)), Some(0));
+        assert_eq!(tab_at(labels, area, Position::new(13, 0)), Some(1));
+        assert_eq!(tab_at(labels, area, Position::new(22, 0)), None);
+        assert_eq!(tab_at(labels, area, Position::new(5, 1)), None);
+    }
+
+    #[test]
+    fn wide_characters_take_two_columns() {
+        let area = Rect::new(0, 0, 40, 1);
+        let labels = ["日本", "b"];
+        // " 日本 " spans 0..6, the divider 6, then " b " spans 7..10
+        assert_eq!(tab_at(labels, area, Position::new(5, 0)), Some(0));
+        assert_eq!(tab_at(labels, area, Position::new(7, 0)), Some(1));
+    }
+}
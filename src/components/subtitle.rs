@@ -1,44 +1,102 @@
+use ratatui::layout::{Position, Rect};
 use ratatui::style::Stylize;
 use ratatui::text::{Line, Span};
 
-#[derive(Clone, Copy, Debug)]
+use crate::keymap::{Command, Keymap, Scope};
+
+#[derive(Clone, Debug)]
 pub(crate) struct SubtitleItem {
-    label: &'static str,
-    trigger: &'static str,
+    command: Command,
+    trigger: String,
 }
 
 impl SubtitleItem {
-    pub const fn new(trigger: &'static str, label: &'static str) -> Self {
-        Self { label, trigger }
+    pub fn new(command: Command, trigger: String) -> Self {
+        Self { command, trigger }
+    }
+
+    pub const fn command(&self) -> Command {
+        self.command
+    }
+
+    pub const fn label(&self) -> &'static str {
+        self.command.label()
+    }
+
+    pub fn trigger(&self) -> &str {
+        &self.trigger
     }
 
-    fn spans(&self) -> impl Iterator<Item = Span<'static>> {
+    fn spans(&self) -> impl Iterator<Item = Span<'_>> {
         [
             " ".into(),
-            self.label.into(),
+            self.label().into(),
             format!(" <{}> ", self.trigger).bold(),
         ]
         .into_iter()
     }
 }
 
-#[derive(Clone, Copy, Debug)]
-pub(crate) struct Subtitle<const N: usize> {
-    items: [SubtitleItem; N],
+/// Hints of the commands, with the first keys they are bound to.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Subtitle {
+    items: Vec<SubtitleItem>,
 }
 
-impl<const N: usize> Subtitle<N> {
-    pub const fn new(items: [SubtitleItem; N]) -> Self {
+impl From<Vec<SubtitleItem>> for Subtitle {
+    fn from(items: Vec<SubtitleItem>) -> Self {
         Self { items }
     }
+}
+
+impl Subtitle {
+    /// Hints of the given commands, skipping the ones bound to no key.
+    pub fn new(keymap: &Keymap, scope: Scope, commands: &[Command]) -> Self {
+        Self {
+            items: Self::items(keymap, scope, commands),
+        }
+    }
+
+    pub fn items(keymap: &Keymap, scope: Scope, commands: &[Command]) -> Vec<SubtitleItem> {
+        commands
+            .iter()
+            .filter_map(|command| {
+                let trigger = keymap.hint(scope, *command)?;
+                Some(SubtitleItem::new(*command, trigger))
+            })
+            .collect()
+    }
+
+    fn item_spans(index: usize, item: &SubtitleItem) -> impl Iterator<Item = Span<'_>> {
+        let separator = if index == 0 { "" } else { "-" };
+        std::iter::once(Span::raw(separator)).chain(item.spans())
+    }
+
+    pub fn line(&self) -> Line<'_> {
+        Line::from_iter(
+            self.items
+                .iter()
+                .enumerate()
+                .flat_map(|(index, item)| Self::item_spans(index, item)),
+        )
+    }
 
-    pub fn line(&self) -> Line {
-        Line::from_iter(self.items.iter().enumerate().flat_map(|(index, item)| {
-            if index == 0 {
-                std::iter::once(Span::raw("")).chain(item.spans())
-            } else {
-                std::iter::once(Span::raw("-")).chain(item.spans())
+    /// Command of the hint under the position, the subtitle being rendered at the bottom of a
+    /// bordered block covering the area.
+    pub fn command_at(&self, area: Rect, position: Position) -> Option<Command> {
+        if area.height == 0 || position.y != area.bottom() - 1 {
+            return None;
+        }
+        let mut x = area.x + 1;
+        for (index, item) in self.items.iter().enumerate() {
+            let width = Self::item_spans(index, item)
+                .map(|span| span.width() as u16)
+                .sum::<u16>();
+            if (x..x + width).contains(&position.x) {
+                return Some(item.command);
             }
-        }))
+            x += width;
+        }
+        None
     }
 }
@@ -0,0 +1,316 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::keymap::Command;
+
+const APP_NAME: &str = "transmission-tui";
+
+/// Location of the configuration file, following the XDG base directory specification.
+pub fn config_path() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|path| path.join(APP_NAME).join("config.toml"))
+}
+
+/// Directory of the logs, following the XDG base directory specification.
+pub fn state_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_STATE_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| {
+            std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("state"))
+        })
+        .map(|path| path.join(APP_NAME))
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ListLayout {
+    #[default]
+    Cards,
+    Table,
+}
+
+impl ListLayout {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Cards => "cards",
+            Self::Table => "table",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Column {
+    Name,
+    Status,
+    Progress,
+    Size,
+    DownloadSpeed,
+    UploadSpeed,
+    Eta,
+    Ratio,
+    Peers,
+    Queue,
+    Label,
+    Tracker,
+    DownloadDir,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ColumnConfig {
+    pub column: Column,
+    /// Width of the column, the name taking the remaining space when not specified.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u16>,
+}
+
+impl ColumnConfig {
+    const fn new(column: Column) -> Self {
+        Self {
+            column,
+            width: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ListConfig {
+    pub layout: ListLayout,
+    /// Field sorting the list, followed by `asc` or `desc`, like `ratio desc`.
+    pub sort: String,
+    /// Columns displayed with the table layout.
+    pub columns: Vec<ColumnConfig>,
+}
+
+impl Default for ListConfig {
+    fn default() -> Self {
+        Self {
+            layout: ListLayout::default(),
+            sort: "queue asc".to_string(),
+            columns: vec![
+                ColumnConfig::new(Column::Name),
+                ColumnConfig::new(Column::Status),
+                ColumnConfig::new(Column::Progress),
+                ColumnConfig::new(Column::Size),
+                ColumnConfig::new(Column::DownloadSpeed),
+                ColumnConfig::new(Column::UploadSpeed),
+                ColumnConfig::new(Column::Eta),
+                ColumnConfig::new(Column::Ratio),
+            ],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeymapPreset {
+    #[default]
+    Default,
+    Vim,
+}
+
+/// Bindings of the preset, overridden per scope by key sequences like `"<C-d>" = "page_down"`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct KeymapConfig {
+    pub preset: KeymapPreset,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub list: BTreeMap<String, Command>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub detail: BTreeMap<String, Command>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub dialog: BTreeMap<String, Command>,
+}
+
+/// Transmission server that can be switched to from the command palette with `:profile <name>`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Profile {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    /// Profile connected to at startup, when `TRANSMISSION_URL` isn't set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
+    /// Asks before quitting while actions sent to the daemon haven't completed yet.
+    pub confirm_quit: bool,
+    /// Filter of the logs, like `debug` or `transmission_tui=trace`, `off` disabling them.
+    pub log_level: String,
+    pub list: ListConfig,
+    pub keymap: KeymapConfig,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            default_profile: None,
+            confirm_quit: true,
+            log_level: "info".to_string(),
+            list: ListConfig::default(),
+            keymap: KeymapConfig::default(),
+            profiles: BTreeMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Loads the configuration file, falling back to the default configuration when missing.
+    pub fn load() -> color_eyre::Result<Self> {
+        let Some(path) = config_path().filter(|path| path.exists()) else {
+            return Ok(Self::default());
+        };
+        let content = std::fs::read_to_string(&path)?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    /// Profile to connect to at startup: the server of the `TRANSMISSION_URL` environment
+    /// variable, given as `url`, or else the default profile.
+    pub fn startup_profile(&self, url: Option<String>) -> color_eyre::Result<Profile> {
+        if let Some(url) = url.filter(|url| !url.is_empty()) {
+            return Ok(Profile {
+                url,
+                username: None,
+                password: None,
+            });
+        }
+        let Some(name) = self.default_profile.as_deref() else {
+            color_eyre::eyre::bail!(
+                "no server to connect to, set TRANSMISSION_URL or the default_profile of the configuration"
+            );
+        };
+        match self.profiles.get(name) {
+            Some(profile) => Ok(profile.clone()),
+            None => color_eyre::eyre::bail!(
+                "the default profile {name:?} isn't declared in the profiles of the configuration"
+            ),
+        }
+    }
+
+    /// Saves a value of the `list` table, leaving the rest of the configuration file untouched.
+    pub fn save_list_value(key: &str, value: &str) -> color_eyre::Result<()> {
+        let Some(path) = config_path() else {
+            return Ok(());
+        };
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err.into()),
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, with_list_value(&content, key, value)?)?;
+        Ok(())
+    }
+}
+
+/// Sets a value of the `list` table in the content of a configuration file, keeping its comments
+/// and formatting.
+fn with_list_value(content: &str, key: &str, value: &str) -> color_eyre::Result<String> {
+    let mut document = content.parse::<toml_edit::DocumentMut>()?;
+    let Some(list) = document
+        .entry("list")
+        .or_insert_with(toml_edit::table)
+        .as_table_like_mut()
+    else {
+        color_eyre::eyre::bail!("the list entry of the configuration file isn't a table");
+    };
+    // replacing the value in place keeps the comments above its key
+    match list.get_mut(key) {
+        Some(item) => *item = toml_edit::value(value),
+        None => {
+            list.insert(key, toml_edit::value(value));
+        }
+    }
+    Ok(document.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Config, ListLayout, with_list_value};
+
+    #[test]
+    fn with_list_value_only_changes_the_value() {
+        let content = r#"# my configuration
+
+[list]
+# toggled with <t>
+layout = "cards"
+sort = "name asc"
+columns = [{ column = "name" }]
+"#;
+        let saved = with_list_value(content, "layout", "table").unwrap();
+        assert_eq!(saved, content.replace(r#""cards""#, r#""table""#));
+        let saved = with_list_value(content, "sort", "ratio desc").unwrap();
+        assert_eq!(saved, content.replace(r#""name asc""#, r#""ratio desc""#));
+    }
+
+    #[test]
+    fn with_list_value_adds_the_missing_table() {
+        let saved = with_list_value("# nothing yet\n", "layout", "table").unwrap();
+        let config = toml::from_str::<Config>(&saved).unwrap();
+        assert_eq!(config.list.layout, ListLayout::Table);
+        assert_eq!(config.list.sort, Config::default().list.sort);
+        assert!(!saved.contains("sort"));
+    }
+
+    #[test]
+    fn with_list_value_supports_the_inline_tables() {
+        let saved = with_list_value("list = { layout = \"table\" }\n", "layout", "cards").unwrap();
+        assert_eq!(saved, "list = { layout = \"cards\" }\n");
+    }
+
+    #[test]
+    fn with_list_value_rejects_an_invalid_file() {
+        assert!(with_list_value("list = [", "layout", "cards").is_err());
+        assert!(with_list_value("list = \"x\"\n", "layout", "cards").is_err());
+        assert!(with_list_value("list = [1, 2]\n", "layout", "cards").is_err());
+    }
+
+    #[test]
+    fn startup_profile_prefers_the_environment() {
+        let config = toml::from_str::<Config>(
+            r#"default_profile = "nas"
+
+[profiles.nas]
+url = "http://nas.local:9091/transmission/rpc"
+username = "admin"
+"#,
+        )
+        .unwrap();
+        let profile = config
+            .startup_profile(Some("http://localhost:9091/transmission/rpc".to_string()))
+            .unwrap();
+        assert_eq!(profile.url, "http://localhost:9091/transmission/rpc");
+        assert_eq!(profile.username, None);
+        let profile = config.startup_profile(None).unwrap();
+        assert_eq!(profile, config.profiles["nas"]);
+    }
+
+    #[test]
+    fn startup_profile_requires_a_server() {
+        let error = Config::default().startup_profile(None).unwrap_err();
+        assert!(error.to_string().contains("TRANSMISSION_URL"));
+        let config = Config {
+            default_profile: Some("nas".to_string()),
+            ..Default::default()
+        };
+        let error = config.startup_profile(Some(String::new())).unwrap_err();
+        assert!(error.to_string().contains("\"nas\""));
+    }
+}
@@ -0,0 +1,294 @@
+use std::fmt;
+
+/// Failure of a request to the daemon, classified to explain it and suggest a remedy.
+#[derive(Debug)]
+pub enum Error {
+    /// Nothing listens on the host and port of the url.
+    ConnectionRefused,
+    /// The host couldn't be reached, like when it can't be resolved.
+    Unreachable(String),
+    Timeout,
+    /// The connection failed while sending the request or reading the response.
+    Network(String),
+    /// HTTP 401, the credentials are missing or wrong.
+    Unauthorized,
+    /// HTTP 403, the host isn't whitelisted by the daemon.
+    Forbidden,
+    /// HTTP 409 again and again, the session id never being accepted.
+    SessionConflict,
+    /// Any other unexpected HTTP status.
+    Http(u16),
+    DuplicateTorrent,
+    InvalidTorrent,
+    /// Result of the RPC other than `success`.
+    Rpc(String),
+    /// The response isn't the JSON expected.
+    Deserialize(serde_json::Error),
+    InvalidUrl(url::ParseError),
+    /// The torrent isn't on the daemon anymore.
+    TorrentNotFound(i64),
+}
+
+/// Innermost cause of an error, which is the most telling one for the network errors.
+fn root_cause(err: &dyn std::error::Error) -> String {
+    let mut cause = err;
+    while let Some(source) = cause.source() {
+        cause = source;
+    }
+    cause.to_string()
+}
+
+impl Error {
+    /// Classifies the result of an RPC that isn't `success`.
+    pub(crate) fn from_result(result: &str) -> Self {
+        match result {
+            "duplicate torrent" => Self::DuplicateTorrent,
+            "invalid or corrupt torrent file" => Self::InvalidTorrent,
+            other => Self::Rpc(other.to_string()),
+        }
+    }
+
+    pub(crate) fn from_status(status: reqwest::StatusCode) -> Self {
+        match status {
+            reqwest::StatusCode::UNAUTHORIZED => Self::Unauthorized,
+            reqwest::StatusCode::FORBIDDEN => Self::Forbidden,
+            other => Self::Http(other.as_u16()),
+        }
+    }
+
+    /// Whether the daemon couldn't be reached for now, so that the request may be retried.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::ConnectionRefused | Self::Unreachable(_) | Self::Timeout | Self::Network(_) => {
+                true
+            }
+            // the gateways answer for the daemon while it restarts
+            Self::Http(status) => matches!(status, 502..=504),
+            _ => false,
+        }
+    }
+
+    /// What can be done about the error, if anything.
+    pub fn remedy(&self) -> Option<&'static str> {
+        match self {
+            Self::ConnectionRefused => {
+                Some("Check that transmission is running and the port of the url")
+            }
+            Self::Unreachable(_) => Some("Check the network and the host of the url"),
+            Self::Timeout | Self::Network(_) => {
+                Some("Check the network, the daemon may also be overloaded")
+            }
+            Self::Unauthorized => Some("Check the username and the password of the profile"),
+            Self::Forbidden => Some("Add this host to the rpc-whitelist of the daemon"),
+            Self::SessionConflict => {
+                Some("Check that no proxy drops the X-Transmission-Session-Id header")
+            }
+            Self::Http(_) => Some("Check that the url ends with /transmission/rpc"),
+            Self::InvalidTorrent => Some("Check the link or the path of the torrent"),
+            Self::Deserialize(_) => {
+                Some("Check that the url points to the RPC of a supported transmission version")
+            }
+            Self::InvalidUrl(_) => Some("Fix the url of the profile in the configuration"),
+            Self::DuplicateTorrent | Self::Rpc(_) | Self::TorrentNotFound(_) => None,
+        }
+    }
+
+    /// Message followed by the remedy, for the notifications.
+    pub fn explain(&self) -> String {
+        match self.remedy() {
+            Some(remedy) => format!("{self}. {remedy}."),
+            None => format!("{self}."),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ConnectionRefused => write!(f, "The daemon refused the connection"),
+            Self::Unreachable(cause) => write!(f, "The daemon can't be reached ({cause})"),
+            Self::Timeout => write!(f, "The daemon took too long to answer"),
+            Self::Network(cause) => write!(f, "The connection to the daemon failed ({cause})"),
+            Self::Unauthorized => write!(f, "The daemon rejected the credentials"),
+            Self::Forbidden => write!(f, "The daemon denied the access to this host"),
+            Self::SessionConflict => write!(f, "The daemon kept renewing the session id"),
+            Self::Http(status) => write!(f, "The daemon answered with the HTTP status {status}"),
+            Self::DuplicateTorrent => write!(f, "The torrent is already on the daemon"),
+            Self::InvalidTorrent => write!(f, "The torrent file is invalid or corrupt"),
+            Self::Rpc(result) => write!(f, "The daemon failed: {result}"),
+            Self::Deserialize(err) => write!(f, "The daemon sent an unexpected response ({err})"),
+            Self::InvalidUrl(err) => write!(f, "The url is invalid ({err})"),
+            Self::TorrentNotFound(id) => write!(f, "The torrent {id} isn't on the daemon anymore"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Deserialize(err) => Some(err),
+            Self::InvalidUrl(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            return Self::Timeout;
+        }
+        let mut source = std::error::Error::source(&err);
+        while let Some(cause) = source {
+            if let Some(io) = cause.downcast_ref::<std::io::Error>()
+                && io.kind() == std::io::ErrorKind::ConnectionRefused
+            {
+                return Self::ConnectionRefused;
+            }
+            source = cause.source();
+        }
+        if err.is_connect() {
+            Self::Unreachable(root_cause(&err))
+        } else {
+            Self::Network(root_cause(&err))
+        }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Deserialize(err)
+    }
+}
+
+impl From<url::ParseError> for Error {
+    fn from(err: url::ParseError) -> Self {
+        Self::InvalidUrl(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::StatusCode;
+    use tokio::net::TcpListener;
+
+    use super::Error;
+
+    async fn post(url: String, timeout: Duration) -> Error {
+        let client = reqwest::Client::builder().timeout(timeout).build().unwrap();
+        client.post(url).send().await.unwrap_err().into()
+    }
+
+    #[test]
+    fn from_status_classifies_the_statuses() {
+        assert!(matches!(
+            Error::from_status(StatusCode::UNAUTHORIZED),
+            Error::Unauthorized
+        ));
+        assert!(matches!(
+            Error::from_status(StatusCode::FORBIDDEN),
+            Error::Forbidden
+        ));
+        assert!(matches!(
+            Error::from_status(StatusCode::NOT_FOUND),
+            Error::Http(404)
+        ));
+    }
+
+    #[test]
+    fn from_result_classifies_the_results() {
+        assert!(matches!(
+            Error::from_result("duplicate torrent"),
+            Error::DuplicateTorrent
+        ));
+        assert!(matches!(
+            Error::from_result("invalid or corrupt torrent file"),
+            Error::InvalidTorrent
+        ));
+        assert!(matches!(
+            Error::from_result("gotNewBlocklist: error"),
+            Error::Rpc(result) if result == "gotNewBlocklist: error"
+        ));
+    }
+
+    #[test]
+    fn is_transient_only_retries_the_unreachable_daemon() {
+        for err in [
+            Error::ConnectionRefused,
+            Error::Unreachable("dns error".to_string()),
+            Error::Timeout,
+            Error::Network("connection reset".to_string()),
+            Error::Http(502),
+            Error::Http(503),
+            Error::Http(504),
+        ] {
+            assert!(err.is_transient(), "{err}");
+        }
+        for err in [
+            Error::Unauthorized,
+            Error::Forbidden,
+            Error::SessionConflict,
+            Error::Http(404),
+            Error::Http(500),
+            Error::DuplicateTorrent,
+            Error::InvalidTorrent,
+            Error::Rpc("failure".to_string()),
+            Error::TorrentNotFound(1),
+        ] {
+            assert!(!err.is_transient(), "{err}");
+        }
+    }
+
+    #[test]
+    fn explain_adds_the_remedy() {
+        assert_eq!(
+            Error::Forbidden.explain(),
+            "The daemon denied the access to this host. Add this host to the rpc-whitelist of \
+             the daemon."
+        );
+        assert_eq!(
+            Error::DuplicateTorrent.explain(),
+            "The torrent is already on the daemon."
+        );
+    }
+
+    #[tokio::test]
+    async fn from_reqwest_detects_the_refused_connections() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+        let err = post(format!("http://{address}/"), Duration::from_secs(5)).await;
+        assert!(matches!(err, Error::ConnectionRefused), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn from_reqwest_detects_the_timeouts() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let err = post(format!("http://{address}/"), Duration::from_millis(100)).await;
+        assert!(matches!(err, Error::Timeout), "{err:?}");
+        drop(listener);
+    }
+
+    #[tokio::test]
+    async fn from_reqwest_detects_the_closed_connections() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            drop(stream);
+        });
+        let err = post(format!("http://{address}/"), Duration::from_secs(5)).await;
+        assert!(matches!(err, Error::Network(_)), "{err:?}");
+        server.await.unwrap();
+    }
+
+    #[test]
+    fn from_url_keeps_the_cause() {
+        let err = Error::from(url::Url::parse("not an url").unwrap_err());
+        assert!(matches!(err, Error::InvalidUrl(_)));
+        assert!(std::error::Error::source(&err).is_some());
+    }
+}
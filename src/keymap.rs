@@ -0,0 +1,1255 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{KeymapConfig, KeymapPreset};
+
+/// Part of the interface having its own bindings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Scope {
+    List,
+    Detail,
+    Dialog,
+}
+
+impl Scope {
+    pub const ALL: [Self; 3] = [Self::List, Self::Detail, Self::Dialog];
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::List => "List",
+            Self::Detail => "Detail",
+            Self::Dialog => "Dialog",
+        }
+    }
+}
+
+/// Named command a key sequence can be bound to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Command {
+    Quit,
+    Help,
+    Log,
+    Inspector,
+    Reload,
+    Open,
+    Back,
+    Menu,
+    Palette,
+    AddTorrent,
+    SpeedLimits,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    First,
+    Last,
+    FocusDetail,
+    ToggleSplit,
+    ToggleLayout,
+    NextGroup,
+    NextSort,
+    ToggleSortDirection,
+    NextFilter,
+    PreviousFilter,
+    Search,
+    NextMatch,
+    PreviousMatch,
+    Query,
+    MarkByQuery,
+    ToggleMark,
+    VisualMark,
+    MarkAll,
+    ClearMarks,
+    Start,
+    Pause,
+    Verify,
+    Move,
+    Labels,
+    Delete,
+    NextTab,
+    PreviousTab,
+    TabOverview,
+    TabFiles,
+    TabPeers,
+    TabTrackers,
+    PreviousTorrent,
+    NextTorrent,
+    Select,
+    Close,
+    Yes,
+    No,
+}
+
+impl Command {
+    pub const ALL: [Self; 52] = [
+        Self::Quit,
+        Self::Help,
+        Self::Log,
+        Self::Inspector,
+        Self::Reload,
+        Self::Open,
+        Self::Back,
+        Self::Menu,
+        Self::Palette,
+        Self::AddTorrent,
+        Self::SpeedLimits,
+        Self::Up,
+        Self::Down,
+        Self::PageUp,
+        Self::PageDown,
+        Self::First,
+        Self::Last,
+        Self::FocusDetail,
+        Self::ToggleSplit,
+        Self::ToggleLayout,
+        Self::NextGroup,
+        Self::NextSort,
+        Self::ToggleSortDirection,
+        Self::NextFilter,
+        Self::PreviousFilter,
+        Self::Search,
+        Self::NextMatch,
+        Self::PreviousMatch,
+        Self::Query,
+        Self::MarkByQuery,
+        Self::ToggleMark,
+        Self::VisualMark,
+        Self::MarkAll,
+        Self::ClearMarks,
+        Self::Start,
+        Self::Pause,
+        Self::Verify,
+        Self::Move,
+        Self::Labels,
+        Self::Delete,
+        Self::NextTab,
+        Self::PreviousTab,
+        Self::TabOverview,
+        Self::TabFiles,
+        Self::TabPeers,
+        Self::TabTrackers,
+        Self::PreviousTorrent,
+        Self::NextTorrent,
+        Self::Select,
+        Self::Close,
+        Self::Yes,
+        Self::No,
+    ];
+
+    /// Name of the command in the configuration and in the command palette.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Quit => "quit",
+            Self::Help => "help",
+            Self::Log => "log",
+            Self::Inspector => "inspector",
+            Self::Reload => "reload",
+            Self::Open => "open",
+            Self::Back => "back",
+            Self::Menu => "menu",
+            Self::Palette => "palette",
+            Self::AddTorrent => "add_torrent",
+            Self::SpeedLimits => "speed_limits",
+            Self::Up => "up",
+            Self::Down => "down",
+            Self::PageUp => "page_up",
+            Self::PageDown => "page_down",
+            Self::First => "first",
+            Self::Last => "last",
+            Self::FocusDetail => "focus_detail",
+            Self::ToggleSplit => "toggle_split",
+            Self::ToggleLayout => "toggle_layout",
+            Self::NextGroup => "next_group",
+            Self::NextSort => "next_sort",
+            Self::ToggleSortDirection => "toggle_sort_direction",
+            Self::NextFilter => "next_filter",
+            Self::PreviousFilter => "previous_filter",
+            Self::Search => "search",
+            Self::NextMatch => "next_match",
+            Self::PreviousMatch => "previous_match",
+            Self::Query => "query",
+            Self::MarkByQuery => "mark_by_query",
+            Self::ToggleMark => "toggle_mark",
+            Self::VisualMark => "visual_mark",
+            Self::MarkAll => "mark_all",
+            Self::ClearMarks => "clear_marks",
+            Self::Start => "start",
+            Self::Pause => "pause",
+            Self::Verify => "verify",
+            Self::Move => "move",
+            Self::Labels => "labels",
+            Self::Delete => "delete",
+            Self::NextTab => "next_tab",
+            Self::PreviousTab => "previous_tab",
+            Self::TabOverview => "tab_overview",
+            Self::TabFiles => "tab_files",
+            Self::TabPeers => "tab_peers",
+            Self::TabTrackers => "tab_trackers",
+            Self::PreviousTorrent => "previous_torrent",
+            Self::NextTorrent => "next_torrent",
+            Self::Select => "select",
+            Self::Close => "close",
+            Self::Yes => "yes",
+            Self::No => "no",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|command| command.name() == name)
+    }
+
+    /// Short label, displayed in the hints.
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Quit => "Quit",
+            Self::Help => "Help",
+            Self::Log => "Log",
+            Self::Inspector => "RPC inspector",
+            Self::Reload => "Reload",
+            Self::Open => "Open",
+            Self::Back => "Back",
+            Self::Menu => "Menu",
+            Self::Palette => "Command",
+            Self::AddTorrent => "Add",
+            Self::SpeedLimits => "Limits",
+            Self::Up => "Up",
+            Self::Down => "Down",
+            Self::PageUp => "Page up",
+            Self::PageDown => "Page down",
+            Self::First => "First",
+            Self::Last => "Last",
+            Self::FocusDetail => "Focus detail",
+            Self::ToggleSplit => "Split",
+            Self::ToggleLayout => "Table",
+            Self::NextGroup => "Group",
+            Self::NextSort => "Sort",
+            Self::ToggleSortDirection => "Sort direction",
+            Self::NextFilter => "Filter",
+            Self::PreviousFilter => "Previous filter",
+            Self::Search => "Search",
+            Self::NextMatch => "Next match",
+            Self::PreviousMatch => "Previous match",
+            Self::Query => "Query",
+            Self::MarkByQuery => "Mark by query",
+            Self::ToggleMark => "Toggle mark",
+            Self::VisualMark => "Visual mark",
+            Self::MarkAll => "Mark all",
+            Self::ClearMarks => "Clear marks",
+            Self::Start => "Start",
+            Self::Pause => "Pause",
+            Self::Verify => "Verify",
+            Self::Move => "Move",
+            Self::Labels => "Labels",
+            Self::Delete => "Delete",
+            Self::NextTab => "Next tab",
+            Self::PreviousTab => "Previous tab",
+            Self::TabOverview => "Overview",
+            Self::TabFiles => "Files",
+            Self::TabPeers => "Peers",
+            Self::TabTrackers => "Trackers",
+            Self::PreviousTorrent => "Previous torrent",
+            Self::NextTorrent => "Next torrent",
+            Self::Select => "Select",
+            Self::Close => "Cancel",
+            Self::Yes => "Yes",
+            Self::No => "No",
+        }
+    }
+
+    /// Sentence describing the command, displayed in the help.
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Quit => "Quit the application, asking first while actions are running",
+            Self::Help => "Show the available commands",
+            Self::Log => "Show the history of the errors and of the action results",
+            Self::Inspector => {
+                "Show the recent calls to the daemon with their request and response"
+            }
+            Self::Reload => "Reload the torrents from the daemon",
+            Self::Open => "Open the selected torrent, or fold the selected group",
+            Self::Back => "Go back to the previous view, or clear the search and the query",
+            Self::Menu => "Show the actions applying to the selected torrent",
+            Self::Palette => "Type a command, like `:start all` or `:sort ratio desc`",
+            Self::AddTorrent => "Add a torrent from a magnet link, an url or a path",
+            Self::SpeedLimits => "Change the speed limits of the daemon",
+            Self::Up => "Select the previous entry",
+            Self::Down => "Select the next entry",
+            Self::PageUp => "Select the entry a page above",
+            Self::PageDown => "Select the entry a page below",
+            Self::First => "Select the first entry",
+            Self::Last => "Select the last entry",
+            Self::FocusDetail => "Move the focus to the detail pane",
+            Self::ToggleSplit => "Show or hide the detail pane next to the list",
+            Self::ToggleLayout => "Switch between the cards and the table",
+            Self::NextGroup => "Group the torrents by another property",
+            Self::NextSort => "Sort the torrents by another field",
+            Self::ToggleSortDirection => "Reverse the sort order",
+            Self::NextFilter => "Show the torrents of the next status",
+            Self::PreviousFilter => "Show the torrents of the previous status",
+            Self::Search => "Search the torrents by name",
+            Self::NextMatch => "Select the next torrent matching the search",
+            Self::PreviousMatch => "Select the previous torrent matching the search",
+            Self::Query => "Filter the torrents with a query",
+            Self::MarkByQuery => "Mark the torrents matching a query",
+            Self::ToggleMark => "Mark or unmark the selected torrent",
+            Self::VisualMark => "Start or end marking a range of torrents",
+            Self::MarkAll => "Mark or unmark every visible torrent",
+            Self::ClearMarks => "Unmark every torrent",
+            Self::Start => "Start the targeted torrents",
+            Self::Pause => "Pause the targeted torrents",
+            Self::Verify => "Verify the local data of the targeted torrents",
+            Self::Move => "Move the data of the targeted torrents",
+            Self::Labels => "Replace the labels of the targeted torrents",
+            Self::Delete => "Remove the targeted torrents",
+            Self::NextTab => "Show the next tab",
+            Self::PreviousTab => "Show the previous tab",
+            Self::TabOverview => "Show the overview tab",
+            Self::TabFiles => "Show the files tab",
+            Self::TabPeers => "Show the peers tab",
+            Self::TabTrackers => "Show the trackers tab",
+            Self::PreviousTorrent => "Show the previous torrent of the list",
+            Self::NextTorrent => "Show the next torrent of the list",
+            Self::Select => "Run the selected entry",
+            Self::Close => "Close the dialog",
+            Self::Yes => "Accept",
+            Self::No => "Decline",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct KeyParseError(String);
+
+impl fmt::Display for KeyParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid key sequence {:?}", self.0)
+    }
+}
+
+impl std::error::Error for KeyParseError {}
+
+#[derive(Debug)]
+pub enum KeymapError {
+    InvalidKeys(KeyParseError),
+    /// The first sequence starts the second one in the scope, which could then never be completed.
+    Conflict(Scope, KeySequence, KeySequence),
+}
+
+impl fmt::Display for KeymapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidKeys(err) => err.fmt(f),
+            Self::Conflict(scope, short, long) => write!(
+                f,
+                "the key sequence {:?} of the {} bindings starts {:?}, which can't be reached",
+                short.to_string(),
+                scope.label().to_lowercase(),
+                long.to_string(),
+            ),
+        }
+    }
+}
+
+impl std::error::Error for KeymapError {}
+
+impl From<KeyParseError> for KeymapError {
+    fn from(err: KeyParseError) -> Self {
+        Self::InvalidKeys(err)
+    }
+}
+
+/// Single key press, with the modifiers that matter to the bindings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KeyPress {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl From<KeyEvent> for KeyPress {
+    fn from(event: KeyEvent) -> Self {
+        // the shift is already applied to characters, and is implied by the back tab
+        let modifiers = match event.code {
+            KeyCode::Char(_) | KeyCode::BackTab => event.modifiers - KeyModifiers::SHIFT,
+            _ => event.modifiers,
+        };
+        Self {
+            code: event.code,
+            modifiers: modifiers
+                & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT),
+        }
+    }
+}
+
+impl KeyPress {
+    fn name(&self) -> String {
+        let mut name = String::new();
+        for (modifier, prefix) in [
+            (KeyModifiers::CONTROL, "C-"),
+            (KeyModifiers::ALT, "A-"),
+            (KeyModifiers::SHIFT, "S-"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                name.push_str(prefix);
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => name.push_str("Space"),
+            KeyCode::Char('<') => name.push_str("lt"),
+            KeyCode::Char(c) => name.push(c),
+            KeyCode::Enter => name.push_str("Enter"),
+            KeyCode::Esc => name.push_str("Esc"),
+            KeyCode::Tab => name.push_str("Tab"),
+            KeyCode::BackTab => name.push_str("S-Tab"),
+            KeyCode::Backspace => name.push_str("Backspace"),
+            KeyCode::Delete => name.push_str("Del"),
+            KeyCode::Insert => name.push_str("Insert"),
+            KeyCode::Up => name.push_str("Up"),
+            KeyCode::Down => name.push_str("Down"),
+            KeyCode::Left => name.push_str("Left"),
+            KeyCode::Right => name.push_str("Right"),
+            KeyCode::Home => name.push_str("Home"),
+            KeyCode::End => name.push_str("End"),
+            KeyCode::PageUp => name.push_str("PageUp"),
+            KeyCode::PageDown => name.push_str("PageDown"),
+            KeyCode::F(number) => name.push_str(&format!("F{number}")),
+            other => name.push_str(&format!("{other:?}")),
+        }
+        name
+    }
+
+    /// Whether the key types or erases text in an input, like a plain character or backspace.
+    pub fn edits_text(&self) -> bool {
+        match self.code {
+            KeyCode::Char(_) => !self
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT),
+            KeyCode::Backspace | KeyCode::Delete => self.modifiers.is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Whether the key is written between angle brackets in a sequence.
+    fn is_named(&self) -> bool {
+        !self.modifiers.is_empty() || !matches!(self.code, KeyCode::Char(c) if c != ' ' && c != '<')
+    }
+
+    fn parse_named(name: &str) -> Option<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = name;
+        while let Some((prefix, tail)) = rest.split_once('-').filter(|(_, tail)| !tail.is_empty()) {
+            modifiers |= match prefix.to_ascii_uppercase().as_str() {
+                "C" => KeyModifiers::CONTROL,
+                "A" | "M" => KeyModifiers::ALT,
+                "S" => KeyModifiers::SHIFT,
+                _ => return None,
+            };
+            rest = tail;
+        }
+        let code = match rest.to_ascii_lowercase().as_str() {
+            "space" => KeyCode::Char(' '),
+            "lt" => KeyCode::Char('<'),
+            "enter" | "cr" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers -= KeyModifiers::SHIFT;
+                KeyCode::BackTab
+            }
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" | "bs" => KeyCode::Backspace,
+            "del" | "delete" => KeyCode::Delete,
+            "insert" => KeyCode::Insert,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            lower if lower.starts_with('f') && lower.len() > 1 => {
+                KeyCode::F(lower[1..].parse().ok()?)
+            }
+            _ => {
+                let mut chars = rest.chars();
+                match (chars.next(), chars.next()) {
+                    // the terminal sends the shifted letter without the shift, like a typed `A`
+                    (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => {
+                        if !c.is_alphabetic() {
+                            return None;
+                        }
+                        modifiers -= KeyModifiers::SHIFT;
+                        KeyCode::Char(c.to_uppercase().next()?)
+                    }
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => return None,
+                }
+            }
+        };
+        Some(Self { code, modifiers })
+    }
+}
+
+/// Keys to press one after the other, written like `gg`, `<C-d>` or `<C-w>l`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeySequence(Vec<KeyPress>);
+
+impl KeySequence {
+    /// Text displayed in the hints, without the angle brackets of a single named key.
+    pub fn hint(&self) -> String {
+        match self.0.as_slice() {
+            [key] => key.name(),
+            _ => self.to_string(),
+        }
+    }
+
+    /// Whether the sequence starts with a key typing or erasing text.
+    pub fn edits_text(&self) -> bool {
+        self.0.first().is_some_and(KeyPress::edits_text)
+    }
+
+    /// Key presses replaying the sequence.
+    pub fn events(&self) -> impl Iterator<Item = KeyEvent> + '_ {
+        self.0
+            .iter()
+            .map(|key| KeyEvent::new(key.code, key.modifiers))
+    }
+}
+
+impl FromStr for KeySequence {
+    type Err = KeyParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let error = || KeyParseError(value.to_string());
+        let mut keys = Vec::new();
+        let mut chars = value.chars();
+        while let Some(c) = chars.next() {
+            let key = if c == '<' {
+                let (name, _) = chars.as_str().split_once('>').ok_or_else(error)?;
+                let key = KeyPress::parse_named(name).ok_or_else(error)?;
+                chars = chars.as_str()[name.len() + 1..].chars();
+                key
+            } else {
+                KeyPress {
+                    code: KeyCode::Char(c),
+                    modifiers: KeyModifiers::NONE,
+                }
+            };
+            keys.push(key);
+        }
+        if keys.is_empty() {
+            return Err(error());
+        }
+        Ok(Self(keys))
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for key in &self.0 {
+            if key.is_named() {
+                write!(f, "<{}>", key.name())?;
+            } else {
+                write!(f, "{}", key.name())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Key read by an input of its own, like a text field, instead of going through the bindings.
+#[derive(Clone, Copy, Debug)]
+pub struct FixedKey {
+    /// Scope the input is opened from.
+    pub scope: Scope,
+    /// Name of the input, like `Search`.
+    pub input: &'static str,
+    pub keys: &'static str,
+    pub description: &'static str,
+}
+
+/// Keys of the inputs, listed in the help along with the bindings.
+pub const FIXED_KEYS: &[FixedKey] = &[
+    FixedKey {
+        scope: Scope::List,
+        input: "Search",
+        keys: "Enter",
+        description: "Keep the search and go back to the list",
+    },
+    FixedKey {
+        scope: Scope::List,
+        input: "Search",
+        keys: "Esc",
+        description: "Clear the search",
+    },
+    FixedKey {
+        scope: Scope::List,
+        input: "Search",
+        keys: "Backspace",
+        description: "Erase the last character, or clear the empty search",
+    },
+    FixedKey {
+        scope: Scope::List,
+        input: "Prompt",
+        keys: "Enter",
+        description: "Apply the prompt",
+    },
+    FixedKey {
+        scope: Scope::List,
+        input: "Prompt",
+        keys: "Tab",
+        description: "Complete the command, or its argument",
+    },
+    FixedKey {
+        scope: Scope::List,
+        input: "Prompt",
+        keys: "Esc",
+        description: "Close the prompt",
+    },
+    FixedKey {
+        scope: Scope::List,
+        input: "Prompt",
+        keys: "Backspace",
+        description: "Erase the last character, or close the empty prompt",
+    },
+    FixedKey {
+        scope: Scope::List,
+        input: "Prompt",
+        keys: "Up",
+        description: "Show the previous value of the history",
+    },
+    FixedKey {
+        scope: Scope::List,
+        input: "Prompt",
+        keys: "Down",
+        description: "Show the next value of the history, or the typed text",
+    },
+    FixedKey {
+        scope: Scope::List,
+        input: "Form",
+        keys: "Enter",
+        description: "Submit the form",
+    },
+    FixedKey {
+        scope: Scope::List,
+        input: "Form",
+        keys: "Esc",
+        description: "Close the form",
+    },
+    FixedKey {
+        scope: Scope::List,
+        input: "Form",
+        keys: "Tab",
+        description: "Complete the field, or focus the next one",
+    },
+    FixedKey {
+        scope: Scope::List,
+        input: "Form",
+        keys: "S-Tab",
+        description: "Focus the previous field",
+    },
+    FixedKey {
+        scope: Scope::List,
+        input: "Form",
+        keys: "Up",
+        description: "Show the previous value of the field history, or focus the previous field",
+    },
+    FixedKey {
+        scope: Scope::List,
+        input: "Form",
+        keys: "Down",
+        description: "Show the next value of the field history, or focus the next field",
+    },
+    FixedKey {
+        scope: Scope::List,
+        input: "Form",
+        keys: "Space",
+        description: "Toggle the checkbox, or choose the next option",
+    },
+    FixedKey {
+        scope: Scope::List,
+        input: "Form",
+        keys: "Left, Right",
+        description: "Choose the previous or next option",
+    },
+    FixedKey {
+        scope: Scope::List,
+        input: "Log",
+        keys: "Tab",
+        description: "Show only the errors, or every entry",
+    },
+    FixedKey {
+        scope: Scope::List,
+        input: "Log",
+        keys: "C-y",
+        description: "Copy the selected entry to the clipboard",
+    },
+    FixedKey {
+        scope: Scope::List,
+        input: "Log",
+        keys: "C-a",
+        description: "Copy the shown entries to the clipboard",
+    },
+    FixedKey {
+        scope: Scope::Detail,
+        input: "Log",
+        keys: "Tab",
+        description: "Show only the errors, or every entry",
+    },
+    FixedKey {
+        scope: Scope::Detail,
+        input: "Log",
+        keys: "C-y",
+        description: "Copy the selected entry to the clipboard",
+    },
+    FixedKey {
+        scope: Scope::Detail,
+        input: "Log",
+        keys: "C-a",
+        description: "Copy the shown entries to the clipboard",
+    },
+];
+
+type Preset = &'static [(Scope, &'static str, Command)];
+
+const DEFAULT_PRESET: Preset = &[
+    (Scope::List, "q", Command::Quit),
+    (Scope::List, "<Esc>", Command::Back),
+    (Scope::List, "?", Command::Help),
+    (Scope::List, "E", Command::Log),
+    (Scope::List, "I", Command::Inspector),
+    (Scope::List, "r", Command::Reload),
+    (Scope::List, "<Enter>", Command::Open),
+    (Scope::List, "m", Command::Menu),
+    (Scope::List, ":", Command::Palette),
+    (Scope::List, "a", Command::AddTorrent),
+    (Scope::List, "b", Command::SpeedLimits),
+    (Scope::List, "<Up>", Command::Up),
+    (Scope::List, "<Down>", Command::Down),
+    (Scope::List, "<PageUp>", Command::PageUp),
+    (Scope::List, "<PageDown>", Command::PageDown),
+    (Scope::List, "<Home>", Command::First),
+    (Scope::List, "<End>", Command::Last),
+    (Scope::List, "<Right>", Command::FocusDetail),
+    (Scope::List, "s", Command::ToggleSplit),
+    (Scope::List, "t", Command::ToggleLayout),
+    (Scope::List, "g", Command::NextGroup),
+    (Scope::List, "o", Command::NextSort),
+    (Scope::List, "O", Command::ToggleSortDirection),
+    (Scope::List, "<Tab>", Command::NextFilter),
+    (Scope::List, "<S-Tab>", Command::PreviousFilter),
+    (Scope::List, "/", Command::Search),
+    (Scope::List, "n", Command::NextMatch),
+    (Scope::List, "N", Command::PreviousMatch),
+    (Scope::List, "f", Command::Query),
+    (Scope::List, "F", Command::MarkByQuery),
+    (Scope::List, "<Space>", Command::ToggleMark),
+    (Scope::List, "v", Command::VisualMark),
+    (Scope::List, "*", Command::MarkAll),
+    (Scope::List, "u", Command::ClearMarks),
+    (Scope::List, "S", Command::Start),
+    (Scope::List, "p", Command::Pause),
+    (Scope::List, "V", Command::Verify),
+    (Scope::List, "M", Command::Move),
+    (Scope::List, "L", Command::Labels),
+    (Scope::List, "d", Command::Delete),
+    (Scope::Detail, "q", Command::Quit),
+    (Scope::Detail, "<Esc>", Command::Back),
+    (Scope::Detail, "?", Command::Help),
+    (Scope::Detail, "E", Command::Log),
+    (Scope::Detail, "I", Command::Inspector),
+    (Scope::Detail, "<Backspace>", Command::Back),
+    (Scope::Detail, "<Left>", Command::Back),
+    (Scope::Detail, "r", Command::Reload),
+    (Scope::Detail, "s", Command::ToggleSplit),
+    (Scope::Detail, "<Up>", Command::Up),
+    (Scope::Detail, "<Down>", Command::Down),
+    (Scope::Detail, "<PageUp>", Command::PageUp),
+    (Scope::Detail, "<PageDown>", Command::PageDown),
+    (Scope::Detail, "<Tab>", Command::NextTab),
+    (Scope::Detail, "<S-Tab>", Command::PreviousTab),
+    (Scope::Detail, "1", Command::TabOverview),
+    (Scope::Detail, "2", Command::TabFiles),
+    (Scope::Detail, "3", Command::TabPeers),
+    (Scope::Detail, "4", Command::TabTrackers),
+    (Scope::Detail, "[", Command::PreviousTorrent),
+    (Scope::Detail, "]", Command::NextTorrent),
+    (Scope::Dialog, "<Up>", Command::Up),
+    (Scope::Dialog, "<Down>", Command::Down),
+    (Scope::Dialog, "<Enter>", Command::Select),
+    (Scope::Dialog, "c", Command::Close),
+    (Scope::Dialog, "<Esc>", Command::Close),
+    (Scope::Dialog, "<Backspace>", Command::Close),
+    (Scope::Dialog, "m", Command::Close),
+    (Scope::Dialog, "y", Command::Yes),
+    (Scope::Dialog, "n", Command::No),
+];
+
+/// Bindings of the default preset replaced by the vim preset.
+const VIM_REMOVED: &[(Scope, &str)] = &[
+    (Scope::List, "r"),
+    (Scope::List, "g"),
+    (Scope::List, "d"),
+    (Scope::Detail, "r"),
+];
+
+const VIM_ADDED: Preset = &[
+    (Scope::List, "R", Command::Reload),
+    (Scope::List, "l", Command::Open),
+    (Scope::List, "k", Command::Up),
+    (Scope::List, "j", Command::Down),
+    (Scope::List, "<C-u>", Command::PageUp),
+    (Scope::List, "<C-d>", Command::PageDown),
+    (Scope::List, "gg", Command::First),
+    (Scope::List, "G", Command::Last),
+    (Scope::List, "<C-w>l", Command::FocusDetail),
+    (Scope::List, "gb", Command::NextGroup),
+    (Scope::List, "dd", Command::Delete),
+    (Scope::Detail, "h", Command::Back),
+    (Scope::Detail, "<C-w>h", Command::Back),
+    (Scope::Detail, "R", Command::Reload),
+    (Scope::Detail, "k", Command::Up),
+    (Scope::Detail, "j", Command::Down),
+    (Scope::Detail, "<C-u>", Command::PageUp),
+    (Scope::Detail, "<C-d>", Command::PageDown),
+    (Scope::Detail, "gt", Command::NextTab),
+    (Scope::Detail, "gT", Command::PreviousTab),
+    (Scope::Detail, "<C-p>", Command::PreviousTorrent),
+    (Scope::Detail, "<C-n>", Command::NextTorrent),
+    (Scope::Dialog, "k", Command::Up),
+    (Scope::Dialog, "j", Command::Down),
+    (Scope::Dialog, "l", Command::Select),
+    (Scope::Dialog, "q", Command::Close),
+];
+
+fn parse_preset(preset: Preset) -> impl Iterator<Item = (Scope, KeySequence, Command)> {
+    preset.iter().map(|(scope, keys, command)| {
+        let keys = keys
+            .parse()
+            .expect("the presets should only contain valid keys");
+        (*scope, keys, *command)
+    })
+}
+
+/// Key sequences bound to commands, for every scope.
+#[derive(Clone, Debug)]
+pub struct Keymap {
+    bindings: Vec<(Scope, KeySequence, Command)>,
+}
+
+impl Keymap {
+    /// Builds the bindings of the preset, with the ones of the configuration on top of them.
+    ///
+    /// A sequence starting a longer one of the same scope is rejected, as the longer one could
+    /// never be completed.
+    pub fn new(config: &KeymapConfig) -> Result<Self, KeymapError> {
+        let mut keymap = Self {
+            bindings: parse_preset(DEFAULT_PRESET).collect(),
+        };
+        if config.preset == KeymapPreset::Vim {
+            let removed = VIM_REMOVED
+                .iter()
+                .map(|(scope, keys)| {
+                    let keys = keys.parse::<KeySequence>();
+                    (
+                        *scope,
+                        keys.expect("the presets should only contain valid keys"),
+                    )
+                })
+                .collect::<Vec<_>>();
+            keymap
+                .bindings
+                .retain(|(scope, keys, _)| !removed.contains(&(*scope, keys.clone())));
+            parse_preset(VIM_ADDED).for_each(|(scope, keys, command)| {
+                keymap.bind(scope, keys, command);
+            });
+        }
+        for (scope, overrides) in [
+            (Scope::List, &config.list),
+            (Scope::Detail, &config.detail),
+            (Scope::Dialog, &config.dialog),
+        ] {
+            for (keys, command) in overrides {
+                keymap.bind(scope, keys.parse()?, *command);
+            }
+        }
+        keymap.check_conflicts()?;
+        Ok(keymap)
+    }
+
+    fn check_conflicts(&self) -> Result<(), KeymapError> {
+        for (scope, short, _) in &self.bindings {
+            let conflict = self
+                .bindings(*scope)
+                .find(|(long, _)| long.0.len() > short.0.len() && long.0.starts_with(&short.0));
+            if let Some((long, _)) = conflict {
+                return Err(KeymapError::Conflict(*scope, short.clone(), long.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Binds the sequence, replacing the command it was bound to.
+    fn bind(&mut self, scope: Scope, keys: KeySequence, command: Command) {
+        self.bindings
+            .retain(|(other_scope, other_keys, _)| (*other_scope, other_keys) != (scope, &keys));
+        self.bindings.push((scope, keys, command));
+    }
+
+    /// Sequences bound to the command, in the order they were bound.
+    pub fn keys(&self, scope: Scope, command: Command) -> impl Iterator<Item = &KeySequence> {
+        self.bindings
+            .iter()
+            .filter(move |(other_scope, _, other)| *other_scope == scope && *other == command)
+            .map(|(_, keys, _)| keys)
+    }
+
+    /// Text describing the first sequence bound to the command, if any.
+    pub fn hint(&self, scope: Scope, command: Command) -> Option<String> {
+        self.keys(scope, command).next().map(KeySequence::hint)
+    }
+
+    /// Commands of the scope with their sequences, in the order they were bound.
+    pub fn bindings(&self, scope: Scope) -> impl Iterator<Item = (&KeySequence, Command)> {
+        self.bindings
+            .iter()
+            .filter(move |(other, _, _)| *other == scope)
+            .map(|(_, keys, command)| (keys, *command))
+    }
+}
+
+/// Result of a key press.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Resolution {
+    Command(Command),
+    /// The keys pressed so far start a longer sequence.
+    Pending,
+    Unbound,
+}
+
+/// Keys pressed so far, waiting for the rest of a sequence.
+#[derive(Clone, Debug, Default)]
+pub struct Chord {
+    pending: Vec<KeyPress>,
+}
+
+impl Chord {
+    /// Adds the key to the pending ones and looks for the command they are bound to.
+    ///
+    /// When the pending keys don't lead anywhere, the last key is looked up on its own.
+    pub fn resolve(&mut self, keymap: &Keymap, scope: Scope, event: KeyEvent) -> Resolution {
+        let key = KeyPress::from(event);
+        self.pending.push(key);
+        let chained = self.pending.len() > 1;
+        match self.lookup(keymap, scope) {
+            Resolution::Unbound if chained => {
+                self.pending = vec![key];
+                self.lookup(keymap, scope)
+            }
+            resolution => resolution,
+        }
+    }
+
+    fn lookup(&mut self, keymap: &Keymap, scope: Scope) -> Resolution {
+        let mut pending = false;
+        for (keys, command) in keymap.bindings(scope) {
+            if keys.0 == self.pending {
+                self.pending.clear();
+                return Resolution::Command(command);
+            }
+            pending |= keys.0.starts_with(&self.pending);
+        }
+        if pending {
+            Resolution::Pending
+        } else {
+            self.pending.clear();
+            Resolution::Unbound
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::{Chord, Command, KeySequence, Keymap, KeymapError, Resolution, Scope};
+    use crate::config::{KeymapConfig, KeymapPreset};
+
+    fn parse(value: &str) -> KeySequence {
+        value.parse().unwrap()
+    }
+
+    fn key(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    fn vim() -> Keymap {
+        Keymap::new(&KeymapConfig {
+            preset: KeymapPreset::Vim,
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn sequences_are_displayed_as_parsed() {
+        for value in [
+            "q", "gg", "<C-d>", "<C-w>l", "<S-Tab>", "<Space>", "<lt>", "<Enter>", "<F5>",
+            "<C-A-x>",
+        ] {
+            assert_eq!(parse(value).to_string(), value);
+        }
+    }
+
+    #[test]
+    fn named_keys_ignore_the_case_and_have_aliases() {
+        assert_eq!(parse("<c-D>"), parse("<C-D>"));
+        assert_eq!(parse("<cr>"), parse("<Enter>"));
+        assert_eq!(parse("<M-x>"), parse("<A-x>"));
+        assert_eq!(parse("<BackTab>"), parse("<S-Tab>"));
+        assert_eq!(parse("<Del>").to_string(), "<Del>");
+    }
+
+    #[test]
+    fn invalid_sequences_are_rejected() {
+        for value in ["", "<>", "<C-d", "<Foo>", "<X-a>", "<Fx>", "<C->"] {
+            assert!(value.parse::<KeySequence>().is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn hints_drop_the_brackets_of_a_single_key() {
+        assert_eq!(parse("<C-d>").hint(), "C-d");
+        assert_eq!(parse("<C-w>l").hint(), "<C-w>l");
+        assert_eq!(parse("gg").hint(), "gg");
+    }
+
+    #[test]
+    fn chord_resolves_the_single_keys() {
+        let keymap = Keymap::new(&KeymapConfig::default()).unwrap();
+        let mut chord = Chord::default();
+        assert_eq!(
+            chord.resolve(&keymap, Scope::List, key('r')),
+            Resolution::Command(Command::Reload)
+        );
+        assert_eq!(
+            chord.resolve(&keymap, Scope::List, key('g')),
+            Resolution::Command(Command::NextGroup)
+        );
+        assert_eq!(
+            chord.resolve(&keymap, Scope::List, key('x')),
+            Resolution::Unbound
+        );
+    }
+
+    #[test]
+    fn chord_ignores_the_shift_of_the_characters() {
+        let keymap = Keymap::new(&KeymapConfig::default()).unwrap();
+        let mut chord = Chord::default();
+        let event = KeyEvent::new(KeyCode::Char('S'), KeyModifiers::SHIFT);
+        assert_eq!(
+            chord.resolve(&keymap, Scope::List, event),
+            Resolution::Command(Command::Start)
+        );
+        let event = KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT);
+        assert_eq!(
+            chord.resolve(&keymap, Scope::List, event),
+            Resolution::Command(Command::PreviousFilter)
+        );
+    }
+
+    #[test]
+    fn chord_waits_for_the_rest_of_a_sequence() {
+        let keymap = vim();
+        let mut chord = Chord::default();
+        assert_eq!(
+            chord.resolve(&keymap, Scope::List, key('g')),
+            Resolution::Pending
+        );
+        assert_eq!(
+            chord.resolve(&keymap, Scope::List, key('g')),
+            Resolution::Command(Command::First)
+        );
+        assert_eq!(
+            chord.resolve(&keymap, Scope::List, key('g')),
+            Resolution::Pending
+        );
+        assert_eq!(
+            chord.resolve(&keymap, Scope::List, key('b')),
+            Resolution::Command(Command::NextGroup)
+        );
+    }
+
+    #[test]
+    fn chord_looks_up_the_last_key_of_a_dead_end() {
+        let keymap = vim();
+        let mut chord = Chord::default();
+        chord.resolve(&keymap, Scope::List, key('g'));
+        assert_eq!(
+            chord.resolve(&keymap, Scope::List, key('j')),
+            Resolution::Command(Command::Down)
+        );
+        chord.resolve(&keymap, Scope::List, key('d'));
+        assert_eq!(
+            chord.resolve(&keymap, Scope::List, key('x')),
+            Resolution::Unbound
+        );
+        assert_eq!(
+            chord.resolve(&keymap, Scope::List, key('q')),
+            Resolution::Command(Command::Quit)
+        );
+    }
+
+    #[test]
+    fn sequences_starting_another_one_are_rejected() {
+        let mut list = BTreeMap::new();
+        list.insert("z".to_string(), Command::Menu);
+        list.insert("zz".to_string(), Command::Quit);
+        let config = KeymapConfig {
+            list,
+            ..Default::default()
+        };
+        assert!(matches!(
+            Keymap::new(&config),
+            Err(KeymapError::Conflict(Scope::List, short, long))
+                if short == parse("z") && long == parse("zz")
+        ));
+        // the sequences of the vim preset only conflict with a key of the same scope
+        let mut detail = BTreeMap::new();
+        detail.insert("g".to_string(), Command::Reload);
+        let config = KeymapConfig {
+            preset: KeymapPreset::Vim,
+            detail,
+            ..Default::default()
+        };
+        assert!(matches!(
+            Keymap::new(&config),
+            Err(KeymapError::Conflict(Scope::Detail, ..))
+        ));
+    }
+
+    #[test]
+    fn presets_have_no_conflicts() {
+        assert!(Keymap::new(&KeymapConfig::default()).is_ok());
+        vim();
+    }
+
+    #[test]
+    fn shifted_letters_are_the_uppercase_ones() {
+        assert_eq!(parse("<S-a>"), parse("A"));
+        assert_eq!(parse("<C-S-a>"), parse("<C-A>"));
+        assert!("<S-1>".parse::<KeySequence>().is_err());
+        let mut list = BTreeMap::new();
+        list.insert("<S-x>".to_string(), Command::Menu);
+        let keymap = Keymap::new(&KeymapConfig {
+            list,
+            ..Default::default()
+        })
+        .unwrap();
+        let event = KeyEvent::new(KeyCode::Char('X'), KeyModifiers::SHIFT);
+        assert_eq!(
+            Chord::default().resolve(&keymap, Scope::List, event),
+            Resolution::Command(Command::Menu)
+        );
+    }
+
+    #[test]
+    fn chord_is_cleared() {
+        let keymap = vim();
+        let mut chord = Chord::default();
+        chord.resolve(&keymap, Scope::List, key('g'));
+        chord.clear();
+        assert_eq!(
+            chord.resolve(&keymap, Scope::List, key('g')),
+            Resolution::Pending
+        );
+    }
+
+    #[test]
+    fn vim_preset_replaces_the_default_bindings() {
+        let keymap = vim();
+        assert_eq!(
+            keymap.hint(Scope::List, Command::Reload).as_deref(),
+            Some("R")
+        );
+        assert_eq!(
+            keymap.hint(Scope::List, Command::Delete).as_deref(),
+            Some("dd")
+        );
+        assert_eq!(
+            keymap.hint(Scope::Detail, Command::Reload).as_deref(),
+            Some("R")
+        );
+        assert!(
+            keymap
+                .keys(Scope::List, Command::NextGroup)
+                .all(|keys| *keys != parse("g"))
+        );
+        // the bindings that aren't replaced are kept
+        assert_eq!(
+            keymap.hint(Scope::List, Command::Quit).as_deref(),
+            Some("q")
+        );
+        let down = keymap
+            .keys(Scope::List, Command::Down)
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(down, vec!["<Down>", "j"]);
+    }
+
+    #[test]
+    fn overrides_replace_the_bindings_of_the_preset() {
+        let mut list = BTreeMap::new();
+        list.insert("q".to_string(), Command::Menu);
+        list.insert("<C-q>".to_string(), Command::Quit);
+        let mut detail = BTreeMap::new();
+        detail.insert("x".to_string(), Command::Back);
+        let keymap = Keymap::new(&KeymapConfig {
+            preset: KeymapPreset::Vim,
+            list,
+            detail,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(
+            keymap.hint(Scope::List, Command::Quit).as_deref(),
+            Some("C-q")
+        );
+        let menu = keymap
+            .keys(Scope::List, Command::Menu)
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(menu, vec!["m", "q"]);
+        assert!(
+            keymap
+                .keys(Scope::Detail, Command::Back)
+                .any(|keys| *keys == parse("x"))
+        );
+        // the other scopes keep their bindings
+        assert_eq!(
+            keymap.hint(Scope::Detail, Command::Quit).as_deref(),
+            Some("q")
+        );
+    }
+
+    #[test]
+    fn invalid_overrides_are_rejected() {
+        let mut dialog = BTreeMap::new();
+        dialog.insert("<Nope>".to_string(), Command::Close);
+        let config = KeymapConfig {
+            dialog,
+            ..Default::default()
+        };
+        assert!(Keymap::new(&config).is_err());
+    }
+}
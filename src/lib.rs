@@ -1,32 +1,125 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 use color_eyre::Result;
-use crossterm::event::KeyCode;
 use futures::{FutureExt, StreamExt};
 use ratatui::Terminal;
 use ratatui::prelude::Backend;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
-use transmission_rpc::types::Torrent;
+use transmission_rpc::types::{Torrent, TorrentAddArgs};
 
+use crate::config::{Config, ListLayout};
+use crate::keymap::{Keymap, Scope};
 use crate::view::View;
 
 mod components;
+pub mod config;
+pub mod error;
+pub mod keymap;
+pub mod logging;
+mod rpc;
 mod runner;
 mod view;
 
 pub enum Action {
-    DeleteTorrent(i64, bool),
+    /// Adds a torrent from a magnet link, an url or a path on the daemon host.
+    AddTorrent(TorrentAddArgs),
+    /// Connects to the server of a profile.
+    Connect(config::Profile),
+    DeleteTorrents(Vec<i64>, bool),
+    /// Sets the location of the torrents, moving their data there when `true`.
+    MoveTorrents(Vec<i64>, String, bool),
     RefreshList,
     RefreshTorrent(i64),
+    /// Sets the download speed limit, in kB/s, removing it when `None`.
+    SetDownloadLimit(Option<i32>),
+    /// Sets the upload speed limit, in kB/s, removing it when `None`.
+    SetUploadLimit(Option<i32>),
+    SetTorrentsLabels(Vec<i64>, Vec<String>),
+    StartTorrents(Vec<i64>),
+    StopTorrents(Vec<i64>),
+    VerifyTorrents(Vec<i64>),
+}
+
+fn torrent_count(ids: &[i64]) -> String {
+    match ids.len() {
+        1 => "1 torrent".to_string(),
+        count => format!("{count} torrents"),
+    }
+}
+
+fn speed_limit(direction: &str, limit: Option<i32>) -> String {
+    match limit {
+        Some(limit) => format!("Limit the {direction} speed to {limit} kB/s"),
+        None => format!("Remove the {direction} speed limit"),
+    }
+}
+
+impl Action {
+    /// Whether the action changes the state of the daemon, rather than only reading it.
+    fn is_change(&self) -> bool {
+        !matches!(self, Self::RefreshList | Self::RefreshTorrent(_))
+    }
+
+    /// What the action does, for the notifications of its result.
+    fn describe(&self) -> String {
+        match self {
+            Self::AddTorrent(_) => "Add the torrent".to_string(),
+            Self::Connect(profile) => format!("Connect to {}", profile.url),
+            Self::DeleteTorrents(ids, true) => {
+                format!("Delete {} and their data", torrent_count(ids))
+            }
+            Self::DeleteTorrents(ids, false) => format!("Delete {}", torrent_count(ids)),
+            Self::MoveTorrents(ids, location, _) => {
+                format!("Move {} to {location}", torrent_count(ids))
+            }
+            Self::RefreshList => "Refresh the list".to_string(),
+            Self::RefreshTorrent(_) => "Refresh the torrent".to_string(),
+            Self::SetDownloadLimit(limit) => speed_limit("download", *limit),
+            Self::SetUploadLimit(limit) => speed_limit("upload", *limit),
+            Self::SetTorrentsLabels(ids, _) => format!("Label {}", torrent_count(ids)),
+            Self::StartTorrents(ids) => format!("Start {}", torrent_count(ids)),
+            Self::StopTorrents(ids) => format!("Pause {}", torrent_count(ids)),
+            Self::VerifyTorrents(ids) => format!("Verify {}", torrent_count(ids)),
+        }
+    }
+}
+
+/// Severity of a notification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Level {
+    Info,
+    Error,
 }
 
 pub struct Context {
     action_sender: UnboundedSender<Action>,
+    /// Number of changes sent to the runner that haven't completed yet.
+    pending: Arc<AtomicUsize>,
     event_sender: UnboundedSender<Event>,
+    config: Config,
+    keymap: Keymap,
 }
 
 impl Context {
+    fn config(&self) -> &Config {
+        &self.config
+    }
+
+    fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
+    fn pending_actions(&self) -> usize {
+        self.pending.load(Ordering::Relaxed)
+    }
+
     fn send_action(&self, action: Action) {
+        if action.is_change() {
+            self.pending.fetch_add(1, Ordering::Relaxed);
+        }
         let _ = self.action_sender.send(action);
     }
 
@@ -35,22 +128,114 @@ impl Context {
     }
 }
 
+#[cfg(test)]
+impl Context {
+    /// Context with the default configuration, along with the receivers of what it sends.
+    fn for_tests() -> (Self, UnboundedReceiver<Action>, UnboundedReceiver<Event>) {
+        let (action_sender, action_receiver) = tokio::sync::mpsc::unbounded_channel();
+        let (event_sender, event_receiver) = tokio::sync::mpsc::unbounded_channel();
+        let config = Config::default();
+        let keymap = Keymap::new(&config.keymap).expect("the default keymap should be valid");
+        let context = Self {
+            action_sender,
+            event_sender,
+            config,
+            keymap,
+            pending: Arc::default(),
+        };
+        (context, action_receiver, event_receiver)
+    }
+}
+
 pub enum Event {
-    ActionError(std::io::Error),
+    /// Closes the current view and goes back to the previous one.
+    Back,
     Noop,
     InputEvent(crossterm::event::Event),
+    /// Quits the application, without asking for a confirmation.
+    Exit,
     InputError(std::io::Error),
-    OpenList,
-    OpenTorrent(i64),
-    TorrentDelete(i64),
-    TorrentDeleteStart(i64),
-    TorrentDeleteError(i64, Box<dyn std::error::Error + std::marker::Send + Sync>),
+    /// Displays a message in a toast and keeps it in the log.
+    Notify(Level, String),
+    /// Persists the layout of the list in the configuration file.
+    ListLayoutChange(ListLayout),
+    /// Persists the sort of the list, like `ratio desc`, in the configuration file.
+    ListSortChange(String),
+    /// Shows the commands available in the scope.
+    OpenHelp(Scope),
+    /// Shows the history of the notifications.
+    OpenLog,
+    /// Shows the recent calls to the daemon.
+    OpenInspector(Scope),
+    /// Opens a torrent, along with the ids of the list in its current order.
+    OpenTorrent(i64, Vec<i64>),
+    /// Asks to quit the application, which is confirmed first while actions are running.
+    Quit,
+    /// Sent every second, to expire the toasts.
+    Tick,
+    /// A request has been sent to the daemon.
+    RpcCall(Box<rpc::RpcCall>),
+    /// The daemon can't be reached, a refresh being retried for the given attempt.
+    Reconnecting(u32),
+    /// The daemon answers again after reconnecting.
+    Reconnected,
+    /// The refreshes stopped being retried without the daemon answering.
+    ReconnectFailed,
+    /// The settings of the session, or the server, changed.
+    SessionChange,
+    SessionChangeError(error::Error),
+    TorrentChange(Vec<i64>),
+    TorrentChangeStart(Vec<i64>),
+    TorrentChangeError(Vec<i64>, error::Error),
+    TorrentDelete(Vec<i64>),
+    TorrentDeleteStart(Vec<i64>),
+    TorrentDeleteError(Vec<i64>, error::Error),
     TorrentUpdate(Box<Torrent>),
     TorrentUpdateStart,
-    TorrentUpdateError(Box<dyn std::error::Error + std::marker::Send + Sync>),
+    TorrentUpdateError(i64, error::Error),
     TorrentListUpdate(Vec<Torrent>),
     TorrentListUpdateStart,
-    TorrentListUpdateError(Box<dyn std::error::Error + std::marker::Send + Sync>),
+    TorrentListUpdateError(error::Error),
+}
+
+impl Event {
+    /// Name of the variant, for the logs.
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Back => "back",
+            Self::Noop => "noop",
+            Self::InputEvent(_) => "input",
+            Self::Exit => "exit",
+            Self::InputError(_) => "input_error",
+            Self::Notify(..) => "notify",
+            Self::ListLayoutChange(_) => "list_layout_change",
+            Self::ListSortChange(_) => "list_sort_change",
+            Self::OpenHelp(_) => "open_help",
+            Self::OpenLog => "open_log",
+            Self::OpenInspector(_) => "open_inspector",
+            Self::OpenTorrent(..) => "open_torrent",
+            Self::Quit => "quit",
+            Self::Tick => "tick",
+            Self::RpcCall(_) => "rpc_call",
+            Self::Reconnecting(_) => "reconnecting",
+            Self::Reconnected => "reconnected",
+            Self::ReconnectFailed => "reconnect_failed",
+            Self::SessionChange => "session_change",
+            Self::SessionChangeError(_) => "session_change_error",
+            Self::TorrentChange(_) => "torrent_change",
+            Self::TorrentChangeStart(_) => "torrent_change_start",
+            Self::TorrentChangeError(..) => "torrent_change_error",
+            Self::TorrentDelete(_) => "torrent_delete",
+            Self::TorrentDeleteStart(_) => "torrent_delete_start",
+            Self::TorrentDeleteError(..) => "torrent_delete_error",
+            Self::TorrentUpdate(_) => "torrent_update",
+            Self::TorrentUpdateStart => "torrent_update_start",
+            Self::TorrentUpdateError(..) => "torrent_update_error",
+            Self::TorrentListUpdate(_) => "torrent_list_update",
+            Self::TorrentListUpdateStart => "torrent_list_update_start",
+            Self::TorrentListUpdateError(_) => "torrent_list_update_error",
+        }
+    }
 }
 
 pub struct Application {
@@ -63,7 +248,10 @@ pub struct Application {
 }
 
 impl Application {
-    pub fn new(client: transmission_rpc::TransClient) -> Self {
+    /// Builds the application, connected to the server of the profile.
+    pub fn new(profile: config::Profile, config: Config) -> Result<Self> {
+        let keymap = Keymap::new(&config.keymap)?;
+        let client = crate::runner::client(profile)?;
         let cancellation_token = CancellationToken::new();
         let stream = crossterm::event::EventStream::new();
         let view = View::default();
@@ -71,35 +259,49 @@ impl Application {
         let (action_sender, action_receiver) = tokio::sync::mpsc::unbounded_channel();
         let (event_sender, event_receiver) = tokio::sync::mpsc::unbounded_channel();
 
+        let pending = Arc::new(AtomicUsize::new(0));
         let context = Context {
-            action_sender,
+            action_sender: action_sender.clone(),
+            pending: pending.clone(),
             event_sender: event_sender.clone(),
+            config,
+            keymap,
         };
 
-        let runner = crate::runner::Runner::new(client, action_receiver, event_sender);
+        let runner = crate::runner::Runner::new(
+            client,
+            action_receiver,
+            action_sender,
+            event_sender,
+            pending,
+        );
         let task = tokio::spawn(async move { runner.run().await });
 
-        Self {
+        Ok(Self {
             cancellation_token,
             context,
             event_receiver,
             stream,
             view,
             task,
-        }
+        })
     }
 }
 
 impl Application {
+    #[tracing::instrument(name = "application", skip_all)]
     pub async fn run<B>(mut self, mut terminal: Terminal<B>) -> Result<()>
     where
         B: Backend,
     {
+        tracing::info!("started");
         self.view.init(&self.context);
+        let mut ticks = tokio::time::interval(std::time::Duration::from_secs(1));
 
         while !self.cancellation_token.is_cancelled() {
             terminal.draw(|frame| self.view.draw(frame))?;
             let crossterm_event = self.stream.next().fuse();
+            let tick = ticks.tick();
 
             tokio::select! {
                 _ = self.cancellation_token.cancelled() => {
@@ -107,19 +309,29 @@ impl Application {
                 }
                 Some(event) = self.event_receiver.recv() => {
                     match event {
-                        Event::ActionError(err) => eprintln!("error: {err:?}"),
+                        Event::ListLayoutChange(layout) => {
+                            self.context.config.list.layout = layout;
+                            if let Err(err) = Config::save_list_value("layout", layout.name()) {
+                                let message = format!("Could not save the configuration: {err}");
+                                self.view.update(Event::Notify(Level::Error, message), &self.context);
+                            }
+                        }
+                        Event::ListSortChange(sort) => {
+                            if let Err(err) = Config::save_list_value("sort", &sort) {
+                                let message = format!("Could not save the configuration: {err}");
+                                self.view.update(Event::Notify(Level::Error, message), &self.context);
+                            }
+                            self.context.config.list.sort = sort;
+                        }
+                        Event::Exit => self.cancellation_token.cancel(),
                         other => self.view.update(other, &self.context),
                     }
 
                 }
+                _ = tick => self.view.update(Event::Tick, &self.context),
                 Some(maybe_event) = crossterm_event => {
                     match maybe_event {
-                        Ok(event) => match event {
-                            crossterm::event::Event::Key(key_event) if key_event.code == KeyCode::Esc => {
-                                self.cancellation_token.cancel();
-                            }
-                            _ => self.view.update(Event::InputEvent(event), &self.context),
-                        }
+                        Ok(event) => self.view.update(Event::InputEvent(event), &self.context),
                         Err(err) => {
                             self.view.update(Event::InputError(err), &self.context);
                         }
@@ -130,6 +342,7 @@ impl Application {
 
         self.task.abort();
         let _ = self.task.await;
+        tracing::info!("stopped");
 
         Ok(())
     }
@@ -0,0 +1,36 @@
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::EnvFilter;
+
+use crate::config::{Config, state_dir};
+
+/// Variable overriding the log level of the configuration.
+pub const LOG_ENV: &str = "TRANSMISSION_TUI_LOG";
+/// Log files kept in the state directory, one per day.
+const MAX_LOG_FILES: usize = 7;
+
+/// Writes the logs to a daily file of the state directory, as the terminal belongs to the
+/// interface. The logs are flushed when the returned guard is dropped.
+pub fn init(config: &Config) -> color_eyre::Result<Option<WorkerGuard>> {
+    let Some(directory) = state_dir() else {
+        return Ok(None);
+    };
+    let filter = match std::env::var(LOG_ENV) {
+        Ok(directives) => EnvFilter::try_new(directives)?,
+        Err(_) => EnvFilter::try_new(&config.log_level)?,
+    };
+    std::fs::create_dir_all(&directory)?;
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("transmission-tui")
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_FILES)
+        .build(directory)?;
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer)
+        .try_init()
+        .map_err(|err| color_eyre::eyre::eyre!(err))?;
+    Ok(Some(guard))
+}
@@ -3,6 +3,7 @@ use color_eyre::Result;
 fn set_panic_hook() {
     let hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic_info| {
+        let _ = crossterm::execute!(std::io::stdout(), crossterm::event::DisableMouseCapture);
         ratatui::restore();
         hook(panic_info);
     }));
@@ -13,14 +14,16 @@ async fn main() -> Result<()> {
     color_eyre::install()?;
     set_panic_hook();
 
+    let config = transmission_tui::config::Config::load()?;
+    let _log_guard = transmission_tui::logging::init(&config)?;
+    let profile = config.startup_profile(std::env::var("TRANSMISSION_URL").ok())?;
+    let application = transmission_tui::Application::new(profile, config)?;
+
     let terminal = ratatui::init();
-    let client = transmission_rpc::TransClient::new(
-        std::env::var("TRANSMISSION_URL").unwrap().parse().unwrap(),
-    );
+    crossterm::execute!(std::io::stdout(), crossterm::event::EnableMouseCapture)?;
 
-    let res = transmission_tui::Application::new(client)
-        .run(terminal)
-        .await;
+    let res = application.run(terminal).await;
+    let _ = crossterm::execute!(std::io::stdout(), crossterm::event::DisableMouseCapture);
     ratatui::restore();
 
     res
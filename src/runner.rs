@@ -1,127 +1,543 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
-use transmission_rpc::types::{Id, TorrentGetField};
+use tokio::task::AbortHandle;
+use tracing::Instrument;
+use transmission_rpc::types::{
+    BasicAuth, Id, SessionSetArgs, TorrentAction, TorrentAddArgs, TorrentGetField, TorrentSetArgs,
+};
+
+const LIST_FIELDS: &[TorrentGetField] = &[
+    TorrentGetField::Id,
+    TorrentGetField::AddedDate,
+    TorrentGetField::DownloadedEver,
+    TorrentGetField::Error,
+    TorrentGetField::ErrorString,
+    TorrentGetField::Eta,
+    TorrentGetField::IsFinished,
+    TorrentGetField::IsStalled,
+    TorrentGetField::Labels,
+    TorrentGetField::LeftUntilDone,
+    TorrentGetField::MetadataPercentComplete,
+    TorrentGetField::Name,
+    TorrentGetField::PeersConnected,
+    TorrentGetField::PeersGettingFromUs,
+    TorrentGetField::PeersSendingToUs,
+    TorrentGetField::PercentDone,
+    TorrentGetField::QueuePosition,
+    TorrentGetField::RateDownload,
+    TorrentGetField::RateUpload,
+    TorrentGetField::RecheckProgress,
+    TorrentGetField::SecondsSeeding,
+    TorrentGetField::SeedRatioMode,
+    TorrentGetField::SeedRatioLimit,
+    TorrentGetField::SizeWhenDone,
+    TorrentGetField::Status,
+    TorrentGetField::TotalSize,
+    TorrentGetField::Trackers,
+    TorrentGetField::DownloadDir,
+    TorrentGetField::UploadedEver,
+    TorrentGetField::UploadRatio,
+    TorrentGetField::WebseedsSendingToUs,
+];
+
+const DETAIL_FIELDS: &[TorrentGetField] = &[
+    TorrentGetField::Id,
+    TorrentGetField::ActivityDate,
+    TorrentGetField::AddedDate,
+    TorrentGetField::Availability,
+    TorrentGetField::Comment,
+    TorrentGetField::CorruptEver,
+    TorrentGetField::Creator,
+    TorrentGetField::DateCreated,
+    TorrentGetField::DesiredAvailable,
+    TorrentGetField::DoneDate,
+    TorrentGetField::DownloadDir,
+    TorrentGetField::DownloadedEver,
+    TorrentGetField::Error,
+    TorrentGetField::ErrorString,
+    TorrentGetField::Eta,
+    TorrentGetField::FileStats,
+    TorrentGetField::Files,
+    TorrentGetField::HashString,
+    TorrentGetField::HaveValid,
+    TorrentGetField::IsFinished,
+    TorrentGetField::IsPrivate,
+    TorrentGetField::IsStalled,
+    TorrentGetField::LeftUntilDone,
+    TorrentGetField::MagnetLink,
+    TorrentGetField::MetadataPercentComplete,
+    TorrentGetField::Name,
+    TorrentGetField::Peers,
+    TorrentGetField::PeersConnected,
+    TorrentGetField::PeersGettingFromUs,
+    TorrentGetField::PeersSendingToUs,
+    TorrentGetField::PercentDone,
+    TorrentGetField::PieceCount,
+    TorrentGetField::PieceSize,
+    TorrentGetField::QueuePosition,
+    TorrentGetField::RateDownload,
+    TorrentGetField::RateUpload,
+    TorrentGetField::RecheckProgress,
+    TorrentGetField::SecondsDownloading,
+    TorrentGetField::SecondsSeeding,
+    TorrentGetField::SeedRatioMode,
+    TorrentGetField::SeedRatioLimit,
+    TorrentGetField::SizeWhenDone,
+    TorrentGetField::StartDate,
+    TorrentGetField::Status,
+    TorrentGetField::TotalSize,
+    TorrentGetField::TrackerStats,
+    TorrentGetField::UploadedEver,
+    TorrentGetField::UploadRatio,
+    TorrentGetField::Webseeds,
+    TorrentGetField::WebseedsSendingToUs,
+];
+
+/// Retries of a refresh before reporting its failure.
+const MAX_RETRIES: u32 = 4;
+const BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// Delay before a reconnection attempt, doubling with each attempt, and shortened by up to a
+/// half at random so that the clients of a restarting daemon don't all retry at once.
+fn backoff(attempt: u32) -> Duration {
+    let delay = BASE_DELAY.saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)));
+    delay.mul_f64(rand::random_range(0.5..=1.0))
+}
+
+/// A refresh retried while the daemon can't be reached.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Refresh {
+    List,
+    Torrent(i64),
+}
+
+impl Refresh {
+    fn action(self) -> crate::Action {
+        match self {
+            Self::List => crate::Action::RefreshList,
+            Self::Torrent(id) => crate::Action::RefreshTorrent(id),
+        }
+    }
+}
+
+/// Reconnection of a refresh, with its retry waiting for its delay.
+struct Retry {
+    attempt: u32,
+    handle: AbortHandle,
+}
+
+fn rpc_ids(ids: &[i64]) -> Vec<Id> {
+    ids.iter().copied().map(Id::Id).collect()
+}
+
+/// Client of the server of the profile, authenticated when it has a username.
+pub(crate) fn client(
+    profile: crate::config::Profile,
+) -> Result<crate::rpc::Client, url::ParseError> {
+    let auth = profile.username.map(|user| BasicAuth {
+        user,
+        password: profile.password.unwrap_or_default(),
+    });
+    Ok(crate::rpc::Client::new(profile.url.parse()?, auth))
+}
+
+/// Notification of the result of a change.
+fn notification(description: String, event: &crate::Event) -> crate::Event {
+    match event {
+        crate::Event::TorrentChangeError(_, err)
+        | crate::Event::TorrentDeleteError(_, err)
+        | crate::Event::SessionChangeError(err) => {
+            let message = format!("{description} failed: {}", err.explain());
+            crate::Event::Notify(crate::Level::Error, message)
+        }
+        _ => crate::Event::Notify(crate::Level::Info, format!("{description}: done")),
+    }
+}
 
 pub(crate) struct Runner {
-    client: transmission_rpc::TransClient,
+    client: crate::rpc::Client,
     action_receiver: UnboundedReceiver<crate::Action>,
+    /// Sends back the refreshes to retry once their delay elapsed.
+    action_sender: UnboundedSender<crate::Action>,
     event_sender: UnboundedSender<crate::Event>,
+    /// Number of changes not completed yet, incremented when sending them.
+    pending: Arc<AtomicUsize>,
+    /// Reconnections of the refreshes failing since the daemon last answered, a refresh having a
+    /// single retry scheduled at a time.
+    retries: HashMap<Refresh, Retry>,
 }
 
 impl Runner {
     pub(crate) fn new(
-        client: transmission_rpc::TransClient,
+        client: crate::rpc::Client,
         action_receiver: UnboundedReceiver<crate::Action>,
+        action_sender: UnboundedSender<crate::Action>,
         event_sender: UnboundedSender<crate::Event>,
+        pending: Arc<AtomicUsize>,
     ) -> Self {
         Self {
             client,
             action_receiver,
+            action_sender,
             event_sender,
+            pending,
+            retries: HashMap::new(),
         }
     }
 
-    async fn delete_torrent(&mut self, id: i64, delete_local_data: bool) -> crate::Event {
-        let _ = self.event_sender.send(crate::Event::TorrentDeleteStart(id));
+    /// Sends the calls made to the daemon to the inspector.
+    fn forward_calls(&mut self) {
+        for call in self.client.take_calls() {
+            let _ = self
+                .event_sender
+                .send(crate::Event::RpcCall(Box::new(call)));
+        }
+    }
+
+    /// Schedules the retry of a refresh which couldn't reach the daemon, after a backoff and up
+    /// to [`MAX_RETRIES`] times until the daemon answers again. The retry is sent back to the
+    /// action queue, so that the actions sent meanwhile don't wait for it. Returns the event
+    /// reporting the retry, or `None` when the failure is to be reported.
+    ///
+    /// The retry already scheduled for the refresh, when it was sent again meanwhile, is
+    /// cancelled.
+    fn retry(&mut self, refresh: Refresh, err: &crate::error::Error) -> Option<crate::Event> {
+        let previous = self.retries.remove(&refresh);
+        let attempt = previous.as_ref().map_or(0, |retry| retry.attempt);
+        if let Some(retry) = previous {
+            retry.handle.abort();
+        }
+        if !err.is_transient() || attempt >= MAX_RETRIES {
+            if attempt > 0 && self.retries.is_empty() {
+                tracing::warn!(attempts = attempt, error = %err, "reconnection failed");
+                let _ = self.event_sender.send(crate::Event::ReconnectFailed);
+            }
+            return None;
+        }
+        let attempt = attempt + 1;
+        tracing::warn!(attempt, error = %err, "reconnecting");
+        let delay = backoff(attempt);
+        let action_sender = self.action_sender.clone();
+        let handle = tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            let _ = action_sender.send(refresh.action());
+        })
+        .abort_handle();
+        self.retries.insert(refresh, Retry { attempt, handle });
+        Some(crate::Event::Reconnecting(attempt))
+    }
+
+    /// Ends the reconnection of a refresh answered by the daemon, cancelling its retry.
+    fn answered(&mut self, refresh: Refresh) {
+        if let Some(retry) = self.retries.remove(&refresh) {
+            retry.handle.abort();
+            if self.retries.is_empty() {
+                tracing::info!(attempts = retry.attempt, "reconnected");
+                let _ = self.event_sender.send(crate::Event::Reconnected);
+            }
+        }
+    }
+
+    async fn delete_torrents(&mut self, ids: Vec<i64>, delete_local_data: bool) -> crate::Event {
+        let _ = self
+            .event_sender
+            .send(crate::Event::TorrentDeleteStart(ids.clone()));
         match self
             .client
-            .torrent_remove(vec![Id::Id(id)], delete_local_data)
+            .torrent_remove(rpc_ids(&ids), delete_local_data)
             .await
         {
-            Ok(_) => crate::Event::TorrentDelete(id),
-            Err(err) => crate::Event::TorrentDeleteError(id, err),
+            Ok(_) => crate::Event::TorrentDelete(ids),
+            Err(err) => crate::Event::TorrentDeleteError(ids, err),
+        }
+    }
+
+    async fn torrent_action(&mut self, ids: Vec<i64>, action: TorrentAction) -> crate::Event {
+        let _ = self
+            .event_sender
+            .send(crate::Event::TorrentChangeStart(ids.clone()));
+        match self.client.torrent_action(action, rpc_ids(&ids)).await {
+            Ok(_) => crate::Event::TorrentChange(ids),
+            Err(err) => crate::Event::TorrentChangeError(ids, err),
+        }
+    }
+
+    async fn move_torrents(
+        &mut self,
+        ids: Vec<i64>,
+        location: String,
+        move_data: bool,
+    ) -> crate::Event {
+        let _ = self
+            .event_sender
+            .send(crate::Event::TorrentChangeStart(ids.clone()));
+        match self
+            .client
+            .torrent_set_location(rpc_ids(&ids), location, move_data)
+            .await
+        {
+            Ok(_) => crate::Event::TorrentChange(ids),
+            Err(err) => crate::Event::TorrentChangeError(ids, err),
+        }
+    }
+
+    async fn set_labels(&mut self, ids: Vec<i64>, labels: Vec<String>) -> crate::Event {
+        let _ = self
+            .event_sender
+            .send(crate::Event::TorrentChangeStart(ids.clone()));
+        let mut args = TorrentSetArgs::default();
+        args.labels = Some(labels);
+        match self.client.torrent_set(args, rpc_ids(&ids)).await {
+            Ok(_) => crate::Event::TorrentChange(ids),
+            Err(err) => crate::Event::TorrentChangeError(ids, err),
+        }
+    }
+
+    async fn add_torrent(&mut self, args: TorrentAddArgs) -> crate::Event {
+        let _ = self
+            .event_sender
+            .send(crate::Event::TorrentChangeStart(Vec::new()));
+        match self.client.torrent_add(args).await {
+            Ok(_) => crate::Event::TorrentChange(Vec::new()),
+            Err(err) => crate::Event::TorrentChangeError(Vec::new(), err),
+        }
+    }
+
+    async fn set_speed_limit(&mut self, download: bool, limit: Option<i32>) -> crate::Event {
+        let mut args = SessionSetArgs::default();
+        if download {
+            args.speed_limit_down_enabled = Some(limit.is_some());
+            args.speed_limit_down = limit;
+        } else {
+            args.speed_limit_up_enabled = Some(limit.is_some());
+            args.speed_limit_up = limit;
+        }
+        match self.client.session_set(args).await {
+            Ok(_) => crate::Event::SessionChange,
+            Err(err) => crate::Event::SessionChangeError(err),
+        }
+    }
+
+    fn connect(&mut self, profile: crate::config::Profile) -> crate::Event {
+        match client(profile) {
+            Ok(client) => {
+                self.client = client;
+                // the retries were meant for the previous daemon
+                if !self.retries.is_empty() {
+                    for (_, retry) in self.retries.drain() {
+                        retry.handle.abort();
+                    }
+                    let _ = self.event_sender.send(crate::Event::ReconnectFailed);
+                }
+                crate::Event::SessionChange
+            }
+            Err(err) => crate::Event::SessionChangeError(err.into()),
         }
     }
 
     async fn refresh_list(&mut self) -> crate::Event {
         let _ = self.event_sender.send(crate::Event::TorrentListUpdateStart);
-        let fields = vec![
-            TorrentGetField::Id,
-            TorrentGetField::Error,
-            TorrentGetField::ErrorString,
-            TorrentGetField::Eta,
-            TorrentGetField::IsFinished,
-            TorrentGetField::IsStalled,
-            TorrentGetField::LeftUntilDone,
-            TorrentGetField::MetadataPercentComplete,
-            TorrentGetField::Name,
-            TorrentGetField::PeersConnected,
-            TorrentGetField::PeersGettingFromUs,
-            TorrentGetField::PeersSendingToUs,
-            TorrentGetField::PercentDone,
-            TorrentGetField::QueuePosition,
-            TorrentGetField::RateDownload,
-            TorrentGetField::RateUpload,
-            TorrentGetField::RecheckProgress,
-            TorrentGetField::SeedRatioMode,
-            TorrentGetField::SeedRatioLimit,
-            TorrentGetField::SizeWhenDone,
-            TorrentGetField::Status,
-            TorrentGetField::TotalSize,
-            // TorrentGetField::Trackers,
-            TorrentGetField::DownloadDir,
-            TorrentGetField::UploadedEver,
-            TorrentGetField::UploadRatio,
-            TorrentGetField::WebseedsSendingToUs,
-        ];
-        match self.client.torrent_get(Some(fields), None).await {
-            Ok(list) => crate::Event::TorrentListUpdate(list.arguments.torrents),
-            Err(err) => crate::Event::TorrentListUpdateError(err),
+        match self.client.torrent_get(LIST_FIELDS, None).await {
+            Ok(list) => {
+                self.answered(Refresh::List);
+                crate::Event::TorrentListUpdate(list.arguments.torrents)
+            }
+            Err(err) => self
+                .retry(Refresh::List, &err)
+                .unwrap_or(crate::Event::TorrentListUpdateError(err)),
         }
     }
 
     async fn refresh_torrent(&mut self, id: i64) -> crate::Event {
         let _ = self.event_sender.send(crate::Event::TorrentUpdateStart);
-        let fields = vec![
-            TorrentGetField::Id,
-            TorrentGetField::Error,
-            TorrentGetField::ErrorString,
-            TorrentGetField::Eta,
-            TorrentGetField::IsFinished,
-            TorrentGetField::IsStalled,
-            TorrentGetField::LeftUntilDone,
-            TorrentGetField::MetadataPercentComplete,
-            TorrentGetField::Name,
-            TorrentGetField::PeersConnected,
-            TorrentGetField::PeersGettingFromUs,
-            TorrentGetField::PeersSendingToUs,
-            TorrentGetField::PercentDone,
-            TorrentGetField::QueuePosition,
-            TorrentGetField::RateDownload,
-            TorrentGetField::RateUpload,
-            TorrentGetField::RecheckProgress,
-            TorrentGetField::SeedRatioMode,
-            TorrentGetField::SeedRatioLimit,
-            TorrentGetField::SizeWhenDone,
-            TorrentGetField::Status,
-            TorrentGetField::TotalSize,
-            // TorrentGetField::Trackers,
-            TorrentGetField::DownloadDir,
-            TorrentGetField::UploadedEver,
-            TorrentGetField::UploadRatio,
-            TorrentGetField::WebseedsSendingToUs,
-        ];
-        match self
+        let refresh = Refresh::Torrent(id);
+        let list = self
             .client
-            .torrent_get(Some(fields), Some(vec![Id::Id(id)]))
-            .await
-        {
+            .torrent_get(DETAIL_FIELDS, Some(vec![Id::Id(id)]))
+            .await;
+        match list {
             Ok(mut list) => {
-                crate::Event::TorrentUpdate(Box::from(list.arguments.torrents.pop().unwrap()))
+                self.answered(refresh);
+                match list.arguments.torrents.pop() {
+                    Some(torrent) => crate::Event::TorrentUpdate(Box::new(torrent)),
+                    None => crate::Event::TorrentUpdateError(
+                        id,
+                        crate::error::Error::TorrentNotFound(id),
+                    ),
+                }
             }
-            Err(err) => crate::Event::TorrentUpdateError(err),
+            Err(err) => self
+                .retry(refresh, &err)
+                .unwrap_or(crate::Event::TorrentUpdateError(id, err)),
         }
     }
 
     pub(crate) async fn run(mut self) {
         while let Some(action) = self.action_receiver.recv().await {
-            let event = match action {
-                crate::Action::RefreshList => self.refresh_list().await,
-                crate::Action::RefreshTorrent(id) => self.refresh_torrent(id).await,
-                crate::Action::DeleteTorrent(id, delete_local_data) => {
-                    self.delete_torrent(id, delete_local_data).await
+            let change = action.is_change();
+            let description = action.describe();
+            let span = tracing::info_span!("action", action = %description);
+            let event = async {
+                match action {
+                    crate::Action::AddTorrent(args) => self.add_torrent(args).await,
+                    crate::Action::Connect(profile) => self.connect(profile),
+                    crate::Action::SetDownloadLimit(limit) => {
+                        self.set_speed_limit(true, limit).await
+                    }
+                    crate::Action::SetUploadLimit(limit) => {
+                        self.set_speed_limit(false, limit).await
+                    }
+                    crate::Action::RefreshList => self.refresh_list().await,
+                    crate::Action::RefreshTorrent(id) => self.refresh_torrent(id).await,
+                    crate::Action::DeleteTorrents(ids, delete_local_data) => {
+                        self.delete_torrents(ids, delete_local_data).await
+                    }
+                    crate::Action::MoveTorrents(ids, location, move_data) => {
+                        self.move_torrents(ids, location, move_data).await
+                    }
+                    crate::Action::SetTorrentsLabels(ids, labels) => {
+                        self.set_labels(ids, labels).await
+                    }
+                    crate::Action::StartTorrents(ids) => {
+                        self.torrent_action(ids, TorrentAction::Start).await
+                    }
+                    crate::Action::StopTorrents(ids) => {
+                        self.torrent_action(ids, TorrentAction::Stop).await
+                    }
+                    crate::Action::VerifyTorrents(ids) => {
+                        self.torrent_action(ids, TorrentAction::Verify).await
+                    }
                 }
-            };
+            }
+            .instrument(span)
+            .await;
+            self.forward_calls();
+            if change {
+                self.pending.fetch_sub(1, Ordering::Relaxed);
+                let _ = self.event_sender.send(notification(description, &event));
+            }
             let _ = self.event_sender.send(event);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicUsize;
+
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc::{UnboundedReceiver, unbounded_channel};
+
+    use super::{BASE_DELAY, MAX_RETRIES, Refresh, Runner};
+
+    /// Runner of a daemon refusing the connections.
+    async fn unreachable() -> (Runner, UnboundedReceiver<crate::Event>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        drop(listener);
+        let client = crate::rpc::Client::new(url.parse().unwrap(), None);
+        let (action_sender, action_receiver) = unbounded_channel();
+        let (event_sender, event_receiver) = unbounded_channel();
+        let pending = Arc::new(AtomicUsize::new(0));
+        let runner = Runner::new(
+            client,
+            action_receiver,
+            action_sender,
+            event_sender,
+            pending,
+        );
+        (runner, event_receiver)
+    }
+
+    fn reconnect_failed(events: &mut UnboundedReceiver<crate::Event>) -> bool {
+        std::iter::from_fn(|| events.try_recv().ok())
+            .any(|event| matches!(event, crate::Event::ReconnectFailed))
+    }
+
+    #[tokio::test]
+    async fn retries_are_bounded_per_outage() {
+        let (mut runner, mut events) = unreachable().await;
+        for attempt in 1..=MAX_RETRIES {
+            let event = runner.refresh_list().await;
+            assert!(matches!(event, crate::Event::Reconnecting(n) if n == attempt));
+        }
+        let event = runner.refresh_list().await;
+        assert!(matches!(
+            event,
+            crate::Event::TorrentListUpdateError(crate::error::Error::ConnectionRefused)
+        ));
+        assert!(reconnect_failed(&mut events));
+        assert!(runner.retries.is_empty());
+
+        // the next outage starts over
+        let event = runner.refresh_list().await;
+        assert!(matches!(event, crate::Event::Reconnecting(1)));
+    }
+
+    #[tokio::test]
+    async fn retries_are_counted_per_refresh() {
+        let (mut runner, mut events) = unreachable().await;
+        assert!(matches!(
+            runner.refresh_list().await,
+            crate::Event::Reconnecting(1)
+        ));
+        assert!(matches!(
+            runner.refresh_torrent(1).await,
+            crate::Event::Reconnecting(1)
+        ));
+        for _ in 1..MAX_RETRIES {
+            runner.refresh_list().await;
+        }
+        runner.refresh_list().await;
+        // the torrent is still retried
+        assert!(!reconnect_failed(&mut events));
+        let attempt = runner
+            .retries
+            .get(&Refresh::Torrent(1))
+            .map(|retry| retry.attempt);
+        assert_eq!(attempt, Some(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retry_is_sent_back_to_the_queue() {
+        let (mut runner, _events) = unreachable().await;
+        runner.refresh_torrent(7).await;
+        let action = runner.action_receiver.recv().await.unwrap();
+        assert!(matches!(action, crate::Action::RefreshTorrent(7)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn refresh_keeps_a_single_retry() {
+        let (mut runner, _events) = unreachable().await;
+        runner.refresh_list().await;
+        runner.refresh_list().await;
+        tokio::time::sleep(BASE_DELAY * 4).await;
+        assert!(matches!(
+            runner.action_receiver.try_recv(),
+            Ok(crate::Action::RefreshList)
+        ));
+        assert!(runner.action_receiver.try_recv().is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retries_are_cancelled_on_connect_and_on_success() {
+        let (mut runner, mut events) = unreachable().await;
+        runner.refresh_list().await;
+        runner.refresh_torrent(1).await;
+        runner.answered(Refresh::Torrent(1));
+        runner.connect(crate::config::Profile {
+            url: "http://localhost:9091/transmission/rpc".to_string(),
+            username: None,
+            password: None,
+        });
+        assert!(runner.retries.is_empty());
+        assert!(reconnect_failed(&mut events));
+        tokio::time::sleep(BASE_DELAY * 4).await;
+        assert!(runner.action_receiver.try_recv().is_err());
+    }
+}
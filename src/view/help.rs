@@ -0,0 +1,231 @@
+use crossterm::event::{Event, KeyCode, MouseEventKind};
+use ratatui::layout::{Constraint, Flex, Layout};
+use ratatui::prelude::{Buffer, Rect};
+use ratatui::style::{Color, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::block::Title;
+use ratatui::widgets::{Block, Clear, Padding, StatefulWidget, Widget};
+
+use crate::components::list::{List, ListState};
+use crate::keymap::{FIXED_KEYS, Keymap, Scope};
+
+const KEYS_WIDTH: usize = 18;
+const LABEL_WIDTH: usize = 18;
+
+/// Command of the scope, or key of one of its inputs, with every sequence it is bound to.
+#[derive(Clone, Debug)]
+struct HelpEntry {
+    keys: String,
+    /// Label of the command, or name of the input reading the keys.
+    label: &'static str,
+    description: &'static str,
+}
+
+impl HelpEntry {
+    fn matches(&self, filter: &str) -> bool {
+        [self.keys.as_str(), self.label, self.description]
+            .iter()
+            .any(|text| text.to_lowercase().contains(filter))
+    }
+}
+
+/// Adds the keys to the entry having the same label and description, or to a new one.
+fn push_entry(
+    entries: &mut Vec<HelpEntry>,
+    keys: &str,
+    label: &'static str,
+    description: &'static str,
+) {
+    match entries
+        .iter_mut()
+        .find(|entry| (entry.label, entry.description) == (label, description))
+    {
+        Some(entry) => {
+            entry.keys.push_str(", ");
+            entry.keys.push_str(keys);
+        }
+        None => entries.push(HelpEntry {
+            keys: keys.to_string(),
+            label,
+            description,
+        }),
+    }
+}
+
+impl Widget for &HelpEntry {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        Line::from(vec![
+            format!("{:<KEYS_WIDTH$} ", self.keys).bold(),
+            format!("{:<LABEL_WIDTH$} ", self.label).into(),
+            self.description.fg(Color::Gray),
+        ])
+        .render(area, buf);
+    }
+}
+
+impl crate::components::list::ListItem for &HelpEntry {
+    fn height(&self) -> u16 {
+        1
+    }
+}
+
+/// Overlay listing the commands of a scope, built from the bindings used to dispatch the keys,
+/// followed by the keys of its inputs and dialogs.
+pub(super) struct Help {
+    scope: Scope,
+    entries: Vec<HelpEntry>,
+    /// Entries matching the filter.
+    visible: Vec<HelpEntry>,
+    filter: String,
+    state: ListState,
+}
+
+impl Help {
+    pub(super) fn new(keymap: &Keymap, scope: Scope) -> Self {
+        let mut entries = Vec::new();
+        for (keys, command) in keymap.bindings(scope) {
+            push_entry(
+                &mut entries,
+                &keys.hint(),
+                command.label(),
+                command.description(),
+            );
+        }
+        for key in FIXED_KEYS.iter().filter(|key| key.scope == scope) {
+            push_entry(&mut entries, key.keys, key.input, key.description);
+        }
+        // the menu and the confirmations are only opened from the list
+        if scope == Scope::List {
+            for (keys, command) in keymap.bindings(Scope::Dialog) {
+                push_entry(&mut entries, &keys.hint(), "Dialog", command.description());
+            }
+        }
+        let mut help = Self {
+            scope,
+            visible: Vec::new(),
+            entries,
+            filter: String::new(),
+            state: ListState::default(),
+        };
+        help.apply_filter();
+        help
+    }
+
+    fn apply_filter(&mut self) {
+        let filter = self.filter.to_lowercase();
+        self.visible = self
+            .entries
+            .iter()
+            .filter(|entry| entry.matches(&filter))
+            .cloned()
+            .collect();
+        self.state.select_first(self.visible.len());
+    }
+
+    /// Handles the input, and returns whether the help should be closed.
+    pub(super) fn update(&mut self, event: &Event) -> bool {
+        let len = self.visible.len();
+        match event {
+            Event::Key(key) => match key.code {
+                KeyCode::Esc | KeyCode::Enter => return true,
+                KeyCode::Backspace if self.filter.is_empty() => return true,
+                KeyCode::Backspace => {
+                    self.filter.pop();
+                    self.apply_filter();
+                }
+                KeyCode::Char(c) => {
+                    self.filter.push(c);
+                    self.apply_filter();
+                }
+                KeyCode::Up => self.state.select_previous(len),
+                KeyCode::Down => self.state.select_next(len),
+                KeyCode::PageUp => self.state.select_previous_page(len),
+                KeyCode::PageDown => self.state.select_next_page(len),
+                KeyCode::Home => self.state.select_first(len),
+                KeyCode::End => self.state.select_last(len),
+                _ => {}
+            },
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::ScrollUp => self.state.select_previous(len),
+                MouseEventKind::ScrollDown => self.state.select_next(len),
+                _ => {}
+            },
+            _ => {}
+        }
+        false
+    }
+
+    pub(super) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let vertical = Layout::vertical([Constraint::Percentage(80)]).flex(Flex::Center);
+        let horizontal = Layout::horizontal([Constraint::Max(100)]).flex(Flex::Center);
+        let [area] = vertical.areas(area);
+        let [area] = horizontal.areas(area);
+
+        let footer = if self.filter.is_empty() {
+            Line::from(" Type to search - <Esc> Close ")
+        } else {
+            Line::from(vec![" /".bold(), self.filter.as_str().into(), "█ ".into()])
+        };
+        let block = Block::bordered()
+            .title(Title::from(
+                format!(" Help: {} ", self.scope.label()).bold(),
+            ))
+            .title_bottom(footer)
+            .padding(Padding::horizontal(1));
+        let inner = block.inner(area);
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+        List::new(&self.visible).render(inner, buf, &mut self.state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Help;
+    use crate::config::KeymapConfig;
+    use crate::keymap::{Keymap, Scope};
+
+    fn entry_keys(help: &Help, label: &str, description: &str) -> Option<String> {
+        help.entries
+            .iter()
+            .find(|entry| (entry.label, entry.description) == (label, description))
+            .map(|entry| entry.keys.clone())
+    }
+
+    #[test]
+    fn help_lists_the_bindings_inputs_and_dialogs() {
+        let keymap = Keymap::new(&KeymapConfig::default()).unwrap();
+        let help = Help::new(&keymap, Scope::List);
+        assert_eq!(
+            entry_keys(&help, "Help", "Show the available commands").as_deref(),
+            Some("?")
+        );
+        assert_eq!(
+            entry_keys(&help, "Search", "Clear the search").as_deref(),
+            Some("Esc")
+        );
+        assert_eq!(
+            entry_keys(&help, "Prompt", "Show the previous value of the history").as_deref(),
+            Some("Up")
+        );
+        assert_eq!(
+            entry_keys(&help, "Form", "Close the form").as_deref(),
+            Some("Esc")
+        );
+        assert_eq!(
+            entry_keys(&help, "Dialog", "Close the dialog").as_deref(),
+            Some("c, Esc, Backspace, m")
+        );
+        // the dialogs aren't opened from the detail
+        let help = Help::new(&keymap, Scope::Detail);
+        assert_eq!(
+            entry_keys(&help, "Log", "Copy the selected entry to the clipboard").as_deref(),
+            Some("C-y")
+        );
+        assert!(help.entries.iter().all(|entry| entry.label != "Dialog"));
+    }
+}
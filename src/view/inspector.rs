@@ -0,0 +1,359 @@
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use crossterm::event::{Event, KeyCode, MouseEventKind};
+use ratatui::layout::{Constraint, Flex, Layout};
+use ratatui::prelude::{Buffer, Rect};
+use ratatui::style::{Color, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::block::Title;
+use ratatui::widgets::{Block, Clear, Padding, Paragraph, StatefulWidget, Widget};
+use serde_json::{Value, json};
+
+use crate::components::SIZE_FORMATTER;
+use crate::components::list::{List, ListState};
+use crate::keymap::{Chord, Command, Keymap, Resolution, Scope};
+use crate::rpc::{RpcCall, redact};
+
+/// Calls kept for the inspector, the oldest ones being dropped first, as many refreshes being
+/// kept apart from the other calls.
+const MAX_CALLS: usize = 30;
+const METHOD_WIDTH: usize = 20;
+const STATUS_WIDTH: usize = 16;
+/// Lines scrolled by the page keys in the request and response.
+const PAGE_LINES: isize = 10;
+
+/// Recent calls to the daemon, the most recent first.
+///
+/// The successful refreshes are kept apart, so that the polling can't evict the errors and the
+/// changes.
+#[derive(Default)]
+pub(super) struct Calls {
+    refreshes: VecDeque<Rc<RpcCall>>,
+    others: VecDeque<Rc<RpcCall>>,
+}
+
+impl Calls {
+    pub(super) fn push(&mut self, call: RpcCall) {
+        let items = if call.is_refresh() {
+            &mut self.refreshes
+        } else {
+            &mut self.others
+        };
+        items.push_front(Rc::new(call));
+        items.truncate(MAX_CALLS);
+    }
+
+    /// Calls of both kinds, the most recent first.
+    fn items(&self) -> Vec<Rc<RpcCall>> {
+        let mut items = self
+            .refreshes
+            .iter()
+            .chain(&self.others)
+            .cloned()
+            .collect::<Vec<_>>();
+        items.sort_by_key(|call| std::cmp::Reverse(call.time));
+        items
+    }
+}
+
+fn pretty(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => serde_json::to_string_pretty(other).unwrap_or_default(),
+    }
+}
+
+fn latency(call: &RpcCall) -> String {
+    format!("{} ms", call.latency.as_millis())
+}
+
+/// Call listed in the inspector, with its arguments redacted once.
+#[derive(Clone)]
+struct CallEntry {
+    call: Rc<RpcCall>,
+    arguments: String,
+}
+
+impl CallEntry {
+    fn new(call: Rc<RpcCall>) -> Self {
+        let arguments = redact(call.arguments.clone()).to_string();
+        Self { call, arguments }
+    }
+}
+
+impl Widget for &CallEntry {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let call = &self.call;
+        let status = format!("{:<STATUS_WIDTH$} ", call.status);
+        Line::from(vec![
+            format!("{} ", call.time.format("%H:%M:%S")).fg(Color::Gray),
+            format!("{:<METHOD_WIDTH$} ", call.method).bold(),
+            if call.success {
+                status.green()
+            } else {
+                status.red()
+            },
+            format!("{:>8} ", latency(call)).into(),
+            format!("{:>9} ", SIZE_FORMATTER.format(call.size() as f64)).into(),
+            self.arguments.as_str().fg(Color::Gray),
+        ])
+        .render(area, buf);
+    }
+}
+
+impl crate::components::list::ListItem for &CallEntry {
+    fn height(&self) -> u16 {
+        1
+    }
+}
+
+/// Request and response of a call, scrolled by lines.
+struct Detail {
+    call: Rc<RpcCall>,
+    lines: Vec<String>,
+    /// Index of the line introducing the response.
+    response_line: usize,
+    offset: usize,
+}
+
+impl Detail {
+    fn new(call: Rc<RpcCall>) -> Self {
+        let request = json!({ "method": call.method, "arguments": redact(call.arguments.clone()) });
+        let response = call.redacted_response().map_or_else(
+            || format!("No response: {}", call.status),
+            |response| pretty(&response),
+        );
+        let mut lines = vec!["Request".to_string()];
+        lines.extend(pretty(&request).lines().map(String::from));
+        lines.push(String::new());
+        let response_line = lines.len();
+        lines.push("Response".to_string());
+        lines.extend(response.lines().map(String::from));
+        Self {
+            call,
+            lines,
+            response_line,
+            offset: 0,
+        }
+    }
+
+    fn scroll(&mut self, delta: isize) {
+        let last = self.lines.len().saturating_sub(1);
+        self.offset = self.offset.saturating_add_signed(delta).min(last);
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let lines = self
+            .lines
+            .iter()
+            .enumerate()
+            .skip(self.offset)
+            .take(area.height as usize)
+            .map(|(index, line)| {
+                if index == 0 || index == self.response_line {
+                    Line::from(line.as_str().bold())
+                } else {
+                    Line::from(line.as_str())
+                }
+            })
+            .collect::<Vec<_>>();
+        Paragraph::new(lines).render(area, buf);
+    }
+}
+
+/// Overlay listing the recent calls to the daemon, and the request and response of one of them.
+pub(super) struct Inspector {
+    entries: Vec<CallEntry>,
+    state: ListState,
+    detail: Option<Detail>,
+    /// Scope the inspector was opened from, whose binding closes it.
+    scope: Scope,
+    chord: Chord,
+}
+
+impl Inspector {
+    pub(super) fn new(calls: &Calls, scope: Scope) -> Self {
+        let mut inspector = Self {
+            entries: Vec::new(),
+            state: ListState::default(),
+            detail: None,
+            scope,
+            chord: Chord::default(),
+        };
+        inspector.sync(calls);
+        inspector
+    }
+
+    /// Takes the calls made while the inspector is open, keeping the selected call selected.
+    pub(super) fn sync(&mut self, calls: &Calls) {
+        let selected = self
+            .state
+            .selected()
+            .and_then(|index| self.entries.get(index))
+            .map(|entry| entry.call.clone());
+        let previous = std::mem::take(&mut self.entries);
+        self.entries = calls
+            .items()
+            .into_iter()
+            .map(|call| {
+                previous
+                    .iter()
+                    .find(|entry| Rc::ptr_eq(&entry.call, &call))
+                    .cloned()
+                    .unwrap_or_else(|| CallEntry::new(call))
+            })
+            .collect();
+        let index = selected
+            .and_then(|call| {
+                self.entries
+                    .iter()
+                    .position(|entry| Rc::ptr_eq(&entry.call, &call))
+            })
+            .unwrap_or(0);
+        if self.entries.is_empty() {
+            self.state.select(None);
+        } else {
+            self.state.select(Some(index));
+        }
+    }
+
+    /// Handles the input, and returns whether the inspector should be closed.
+    pub(super) fn update(&mut self, event: &Event, keymap: &Keymap) -> bool {
+        if let Event::Key(key) = event
+            && self.chord.resolve(keymap, self.scope, *key)
+                == Resolution::Command(Command::Inspector)
+        {
+            return true;
+        }
+        if let Some(detail) = self.detail.as_mut() {
+            match event {
+                Event::Key(key) => match key.code {
+                    KeyCode::Esc | KeyCode::Backspace | KeyCode::Left => self.detail = None,
+                    KeyCode::Up => detail.scroll(-1),
+                    KeyCode::Down => detail.scroll(1),
+                    KeyCode::PageUp => detail.scroll(-PAGE_LINES),
+                    KeyCode::PageDown => detail.scroll(PAGE_LINES),
+                    KeyCode::Home => detail.offset = 0,
+                    KeyCode::End => detail.scroll(isize::MAX),
+                    _ => {}
+                },
+                Event::Mouse(mouse) => match mouse.kind {
+                    MouseEventKind::ScrollUp => detail.scroll(-1),
+                    MouseEventKind::ScrollDown => detail.scroll(1),
+                    _ => {}
+                },
+                _ => {}
+            }
+            return false;
+        }
+        let len = self.entries.len();
+        match event {
+            Event::Key(key) => match key.code {
+                KeyCode::Esc => return true,
+                KeyCode::Enter | KeyCode::Right => {
+                    if let Some(entry) = self.state.selected().and_then(|i| self.entries.get(i)) {
+                        self.detail = Some(Detail::new(entry.call.clone()));
+                    }
+                }
+                KeyCode::Up => self.state.select_previous(len),
+                KeyCode::Down => self.state.select_next(len),
+                KeyCode::PageUp => self.state.select_previous_page(len),
+                KeyCode::PageDown => self.state.select_next_page(len),
+                KeyCode::Home => self.state.select_first(len),
+                KeyCode::End => self.state.select_last(len),
+                _ => {}
+            },
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::ScrollUp => self.state.select_previous(len),
+                MouseEventKind::ScrollDown => self.state.select_next(len),
+                _ => {}
+            },
+            _ => {}
+        }
+        false
+    }
+
+    pub(super) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let vertical = Layout::vertical([Constraint::Percentage(80)]).flex(Flex::Center);
+        let horizontal = Layout::horizontal([Constraint::Percentage(90)]).flex(Flex::Center);
+        let [area] = vertical.areas(area);
+        let [area] = horizontal.areas(area);
+
+        let (title, footer) = match self.detail.as_ref() {
+            Some(detail) => (
+                format!(
+                    " {} - {} - {} - {} ",
+                    detail.call.method,
+                    detail.call.status,
+                    latency(&detail.call),
+                    SIZE_FORMATTER.format(detail.call.size() as f64)
+                ),
+                " <Up>/<Down> Scroll - <Esc> Back ",
+            ),
+            None => (
+                " RPC calls ".to_string(),
+                " <Enter> Request and response - <Esc> Close ",
+            ),
+        };
+        let block = Block::bordered()
+            .title(Title::from(title.bold()))
+            .title_bottom(footer)
+            .padding(Padding::horizontal(1));
+        let inner = block.inner(area);
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+        if let Some(detail) = self.detail.as_ref() {
+            detail.render(inner, buf);
+        } else if self.entries.is_empty() {
+            Line::from("No call yet".fg(Color::Gray)).render(inner, buf);
+        } else {
+            List::new(&self.entries).render(inner, buf, &mut self.state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::Local;
+    use serde_json::json;
+
+    use super::{Calls, MAX_CALLS};
+    use crate::rpc::RpcCall;
+
+    fn call(method: &str, success: bool) -> RpcCall {
+        RpcCall {
+            time: Local::now(),
+            method: method.to_string(),
+            arguments: json!({}),
+            status: String::new(),
+            success,
+            latency: Duration::ZERO,
+            response: None,
+        }
+    }
+
+    #[test]
+    fn refreshes_do_not_evict_the_other_calls() {
+        let mut calls = Calls::default();
+        calls.push(call("torrent-get", false));
+        calls.push(call("torrent-start", true));
+        for _ in 0..MAX_CALLS * 2 {
+            calls.push(call("torrent-get", true));
+        }
+        let items = calls.items();
+        assert_eq!(items.len(), MAX_CALLS + 2);
+        assert!(
+            items
+                .iter()
+                .any(|call| call.method == "torrent-get" && !call.success)
+        );
+        assert!(items.iter().any(|call| call.method == "torrent-start"));
+    }
+}
@@ -0,0 +1,142 @@
+use transmission_rpc::types::{Priority, Torrent};
+
+use crate::components::completion::Argument;
+use crate::components::dialog::{Dialog, FieldKind, NumberInput, Select};
+use crate::components::input::TextInput;
+use crate::components::subtitle::Subtitle;
+use crate::components::{SIZE_FORMATTER, parse_speed};
+use crate::keymap::{Command, Keymap, Scope};
+
+const DELETE_OPTIONS: [Command; 3] = [Command::Close, Command::Yes, Command::No];
+/// Names listed in the delete dialog before summing up the other ones.
+const DELETE_NAMES: usize = 5;
+
+pub(super) const DIRECTORY: &str = "Directory";
+pub(super) const MOVE_DATA: &str = "Move the data";
+pub(super) const TORRENT: &str = "Torrent";
+pub(super) const PRIORITY: &str = "Priority";
+pub(super) const PAUSED: &str = "Start paused";
+pub(super) const DOWNLOAD: &str = "Download";
+pub(super) const DOWNLOAD_SPEED: &str = "Download speed";
+pub(super) const UPLOAD: &str = "Upload";
+pub(super) const UPLOAD_SPEED: &str = "Upload speed";
+
+/// Priorities of the add dialog, in the order of its select.
+pub(super) const PRIORITIES: [Priority; 3] = [Priority::Normal, Priority::High, Priority::Low];
+
+/// Speed limit chosen in the limits dialog for a direction.
+pub(super) enum LimitChoice {
+    Unchanged,
+    Unlimited,
+    /// Limit in kB/s.
+    Limited(i32),
+}
+
+/// Dialog opened above the list, with the torrents it applies to.
+pub(super) enum ListDialog {
+    Delete(Vec<i64>),
+    Move(Vec<i64>),
+    Add,
+    Limits,
+}
+
+fn title(action: &str, torrents: &[&Torrent]) -> String {
+    match torrents {
+        [torrent] => format!("{action} {}", torrent.name.as_deref().unwrap_or_default()),
+        _ => format!("{action} {} torrents", torrents.len()),
+    }
+}
+
+pub(super) fn delete(keymap: &Keymap, torrents: &[&Torrent]) -> Dialog {
+    let size = torrents
+        .iter()
+        .map(|torrent| torrent.total_size.unwrap_or(0))
+        .sum::<i64>();
+    let mut message = String::new();
+    if torrents.len() > 1 {
+        for torrent in torrents.iter().take(DELETE_NAMES) {
+            message.push_str(&format!(
+                "• {}\n",
+                torrent.name.as_deref().unwrap_or_default()
+            ));
+        }
+        if torrents.len() > DELETE_NAMES {
+            message.push_str(&format!("and {} more\n", torrents.len() - DELETE_NAMES));
+        }
+        message.push('\n');
+    }
+    message.push_str(&format!(
+        "{} of data affected.\nDelete the local data?",
+        SIZE_FORMATTER.format(size as f64)
+    ));
+    let options = Subtitle::new(keymap, Scope::Dialog, &DELETE_OPTIONS);
+    Dialog::new(title("Delete", torrents), options).with_message(message)
+}
+
+pub(super) fn move_to(
+    keymap: &Keymap,
+    torrents: &[&Torrent],
+    history: &[String],
+    directories: Vec<String>,
+) -> Dialog {
+    let current = torrents
+        .first()
+        .and_then(|torrent| torrent.download_dir.clone())
+        .unwrap_or_default();
+    let directory = TextInput::new(current)
+        .required()
+        .with_history(history.to_vec())
+        .with_completions(directories, Argument::Rest);
+    Dialog::new(title("Move", torrents), Dialog::form_options(keymap))
+        .with_field(DIRECTORY, FieldKind::Text(directory))
+        .with_field(MOVE_DATA, FieldKind::Checkbox(true))
+}
+
+pub(super) fn add(keymap: &Keymap, history: &[String], directories: Vec<String>) -> Dialog {
+    let torrent = TextInput::default()
+        .with_placeholder("magnet link, url or path")
+        .required()
+        .with_history(history.to_vec());
+    let directory = TextInput::default()
+        .with_placeholder("default directory")
+        .with_completions(directories, Argument::Rest);
+    Dialog::new("Add a torrent", Dialog::form_options(keymap))
+        .with_field(TORRENT, FieldKind::Text(torrent))
+        .with_field(DIRECTORY, FieldKind::Text(directory))
+        .with_field(
+            PRIORITY,
+            FieldKind::Select(Select::new(vec!["Normal", "High", "Low"], 0)),
+        )
+        .with_field(PAUSED, FieldKind::Checkbox(false))
+}
+
+fn speed_input() -> FieldKind {
+    let input = TextInput::default().with_placeholder("like 500 or 2M");
+    FieldKind::Number(NumberInput::new(input, "kB/s", |value| {
+        parse_speed(value).map(f64::from)
+    }))
+}
+
+pub(super) fn limits(keymap: &Keymap) -> Dialog {
+    let select = || FieldKind::Select(Select::new(vec!["Unchanged", "Unlimited", "Limited"], 0));
+    Dialog::new("Speed limits", Dialog::form_options(keymap))
+        .with_field(DOWNLOAD, select())
+        .with_field(DOWNLOAD_SPEED, speed_input())
+        .with_field(UPLOAD, select())
+        .with_field(UPLOAD_SPEED, speed_input())
+}
+
+/// Limit chosen for a direction, failing when limited without a speed.
+pub(super) fn chosen_limit(dialog: &mut Dialog, select: &str, speed: &str) -> Option<LimitChoice> {
+    match dialog.selected(select) {
+        0 => Some(LimitChoice::Unchanged),
+        1 => Some(LimitChoice::Unlimited),
+        _ => {
+            let value = dialog.number(speed);
+            if value.is_none() {
+                dialog.fail(speed, format!("{speed} is required"));
+            }
+            value.map(|value| LimitChoice::Limited(value as i32))
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use transmission_rpc::types::{ErrorType, Torrent, TorrentStatus};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(super) enum StatusFilter {
+    #[default]
+    All,
+    Downloading,
+    Seeding,
+    Active,
+    Paused,
+    Queued,
+    Checking,
+    Error,
+}
+
+impl StatusFilter {
+    pub(super) const ALL: [StatusFilter; 8] = [
+        StatusFilter::All,
+        StatusFilter::Downloading,
+        StatusFilter::Seeding,
+        StatusFilter::Active,
+        StatusFilter::Paused,
+        StatusFilter::Queued,
+        StatusFilter::Checking,
+        StatusFilter::Error,
+    ];
+
+    pub(super) const fn label(self) -> &'static str {
+        match self {
+            Self::All => "All",
+            Self::Downloading => "Downloading",
+            Self::Seeding => "Seeding",
+            Self::Active => "Active",
+            Self::Paused => "Paused",
+            Self::Queued => "Queued",
+            Self::Checking => "Checking",
+            Self::Error => "Error",
+        }
+    }
+
+    pub(super) fn index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|filter| *filter == self)
+            .unwrap_or(0)
+    }
+
+    pub(super) fn next(self) -> Self {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    pub(super) fn previous(self) -> Self {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    pub(super) fn matches(self, item: &Torrent) -> bool {
+        let status = item.status.unwrap_or(TorrentStatus::Stopped);
+        match self {
+            Self::All => true,
+            Self::Downloading => matches!(
+                status,
+                TorrentStatus::Downloading | TorrentStatus::QueuedToDownload
+            ),
+            Self::Seeding => matches!(status, TorrentStatus::Seeding | TorrentStatus::QueuedToSeed),
+            // same definition as the transmission web interface
+            Self::Active => {
+                item.peers_getting_from_us.unwrap_or(0) > 0
+                    || item.peers_sending_to_us.unwrap_or(0) > 0
+                    || item.webseeds_sending_to_us.unwrap_or(0) > 0
+                    || status == TorrentStatus::Verifying
+            }
+            Self::Paused => status == TorrentStatus::Stopped,
+            Self::Queued => matches!(
+                status,
+                TorrentStatus::QueuedToDownload | TorrentStatus::QueuedToSeed
+            ),
+            Self::Checking => matches!(
+                status,
+                TorrentStatus::Verifying | TorrentStatus::QueuedToVerify
+            ),
+            Self::Error => !matches!(item.error, None | Some(ErrorType::Ok)),
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::HashSet;
+
+use ratatui::prelude::{Buffer, Rect};
+use ratatui::style::{Color, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::Widget;
+use transmission_rpc::types::{Torrent, TorrentStatus};
+
+use crate::components::list::Entry;
+use crate::components::{SIZE_FORMATTER, SPEED_FORMATTER, torrent_status_label, tracker_hosts};
+use crate::view::list::TorrentItem;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(super) enum GroupBy {
+    #[default]
+    None,
+    DownloadDir,
+    Tracker,
+    Label,
+    Status,
+}
+
+impl GroupBy {
+    const ALL: [GroupBy; 5] = [
+        GroupBy::None,
+        GroupBy::DownloadDir,
+        GroupBy::Tracker,
+        GroupBy::Label,
+        GroupBy::Status,
+    ];
+
+    pub(super) const fn label(self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::DownloadDir => "Directory",
+            Self::Tracker => "Tracker",
+            Self::Label => "Primary label",
+            Self::Status => "Status",
+        }
+    }
+
+    pub(super) fn next(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|group| *group == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Key of the group of a torrent, which is in a single group, the one of its first tracker
+    /// or of its first label.
+    fn key(self, item: &Torrent) -> String {
+        match self {
+            Self::None => String::new(),
+            Self::DownloadDir => item.download_dir.clone().unwrap_or_default(),
+            Self::Tracker => tracker_hosts(item)
+                .next()
+                .unwrap_or_else(|| "No tracker".into()),
+            Self::Label => item
+                .labels
+                .as_ref()
+                .and_then(|labels| labels.first().cloned())
+                .unwrap_or_else(|| "No label".into()),
+            Self::Status => {
+                torrent_status_label(item.status.unwrap_or(TorrentStatus::Stopped)).into()
+            }
+        }
+    }
+}
+
+/// Torrents sharing the same group key, with their aggregated values.
+#[derive(Debug)]
+pub(super) struct Group {
+    pub(super) key: String,
+    pub(super) collapsed: bool,
+    /// Indexes of the items of the group.
+    pub(super) items: Vec<usize>,
+    download: i64,
+    upload: i64,
+    size: i64,
+}
+
+/// Row of the list, either the header of a group or a torrent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Row {
+    Header(usize),
+    Item(usize),
+}
+
+/// Groups the items, keeping their order within each group, and lists the visible rows.
+pub(super) fn build(
+    group_by: GroupBy,
+    items: &[TorrentItem],
+    collapsed: &HashSet<String>,
+) -> (Vec<Group>, Vec<Row>) {
+    if group_by == GroupBy::None {
+        return (Vec::new(), (0..items.len()).map(Row::Item).collect());
+    }
+    let mut groups: Vec<Group> = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        let key = group_by.key(&item.torrent);
+        let group = match groups.iter().position(|group| group.key == key) {
+            Some(position) => &mut groups[position],
+            None => {
+                groups.push(Group {
+                    collapsed: collapsed.contains(&key),
+                    key,
+                    items: Vec::new(),
+                    download: 0,
+                    upload: 0,
+                    size: 0,
+                });
+                groups.last_mut().expect("a group has just been pushed")
+            }
+        };
+        group.items.push(index);
+        group.download += item.torrent.rate_download.unwrap_or(0);
+        group.upload += item.torrent.rate_upload.unwrap_or(0);
+        group.size += item.torrent.total_size.unwrap_or(0);
+    }
+    groups.sort_by_key(|group| group.key.to_lowercase());
+    let rows = groups
+        .iter()
+        .enumerate()
+        .flat_map(|(index, group)| {
+            let items = if group.collapsed {
+                &group.items[..0]
+            } else {
+                &group.items[..]
+            };
+            std::iter::once(Row::Header(index)).chain(items.iter().copied().map(Row::Item))
+        })
+        .collect();
+    (groups, rows)
+}
+
+/// Entries to render for the visible rows, the items being the cards or the table rows.
+pub(super) fn entries<'a, I>(
+    rows: &[Row],
+    groups: &'a [Group],
+    items: &'a [I],
+) -> Vec<Entry<&'a Group, &'a I>> {
+    rows.iter()
+        .map(|row| match *row {
+            Row::Header(index) => Entry::Header(&groups[index]),
+            Row::Item(index) => Entry::Item(&items[index]),
+        })
+        .collect()
+}
+
+impl Widget for &Group {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let marker = if self.collapsed { "▶" } else { "▼" };
+        let download = SPEED_FORMATTER.format(self.download as f64).to_string();
+        let upload = SPEED_FORMATTER.format(self.upload as f64).to_string();
+        let size = SIZE_FORMATTER.format(self.size as f64).to_string();
+        Line::from(vec![
+            format!("{marker} {} ", self.key).bold(),
+            format!(
+                "({}) - 🔻 {download} / 🔺 {upload} - {size}",
+                self.items.len()
+            )
+            .fg(Color::Gray),
+        ])
+        .render(area, buf);
+    }
+}
+
+impl crate::components::list::ListItem for &Group {
+    fn height(&self) -> u16 {
+        1
+    }
+}
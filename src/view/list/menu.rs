@@ -0,0 +1,57 @@
+use transmission_rpc::types::{Torrent, TorrentStatus};
+
+use crate::components::menu::Menu;
+use crate::components::subtitle::SubtitleItem;
+use crate::keymap::Command;
+
+/// Commands listed in the context menu, when they apply to the targeted torrents.
+pub(super) const COMMANDS: [Command; 8] = [
+    Command::Open,
+    Command::Start,
+    Command::Pause,
+    Command::Verify,
+    Command::Move,
+    Command::Labels,
+    Command::ToggleMark,
+    Command::Delete,
+];
+
+/// Whether the command does something on a torrent in its current state.
+pub(super) fn applies_to(command: Command, torrent: &Torrent) -> bool {
+    let status = torrent.status.unwrap_or(TorrentStatus::Stopped);
+    match command {
+        Command::Start => status == TorrentStatus::Stopped,
+        Command::Pause => status != TorrentStatus::Stopped,
+        Command::Verify => !matches!(
+            status,
+            TorrentStatus::Verifying | TorrentStatus::QueuedToVerify
+        ),
+        _ => true,
+    }
+}
+
+/// Context menu being displayed, its entries being computed when opening it.
+#[derive(Debug)]
+pub(super) struct ContextMenu {
+    pub(super) title: String,
+    pub(super) items: Vec<SubtitleItem>,
+    pub(super) selected: usize,
+}
+
+impl ContextMenu {
+    pub(super) fn widget(&self) -> Menu<'_> {
+        Menu::new(&self.title, &self.items, self.selected)
+    }
+
+    pub(super) fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub(super) fn select_next(&mut self) {
+        self.selected = (self.selected + 1).min(self.items.len().saturating_sub(1));
+    }
+
+    pub(super) fn selected_command(&self) -> Option<Command> {
+        self.items.get(self.selected).map(SubtitleItem::command)
+    }
+}
@@ -0,0 +1,1649 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crossterm::event::{Event, KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Constraint, Direction, Layout, Position};
+use ratatui::prelude::{Buffer, Rect};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::symbols::line::THICK;
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::block::Title;
+use ratatui::widgets::{Block, LineGauge, Paragraph, StatefulWidget, Tabs, Widget};
+use transmission_rpc::types::{Torrent, TorrentAddArgs, TorrentStatus};
+
+use crate::Action;
+use crate::components::completion::{Argument, complete_with};
+use crate::components::dialog::Dialog;
+use crate::components::list::{List, ListState};
+use crate::components::mouse::{ClickTracker, tab_at};
+use crate::components::subtitle::{Subtitle, SubtitleItem};
+use crate::components::{SIZE_FORMATTER, SPEED_FORMATTER, torrent_status_label, tracker_hosts};
+use crate::config::{ColumnConfig, ListLayout};
+use crate::keymap::{Chord, Command, Resolution, Scope};
+use crate::view::list::dialogs::{LimitChoice, ListDialog};
+use crate::view::list::filter::StatusFilter;
+use crate::view::list::group::{Group, GroupBy, Row};
+use crate::view::list::menu::ContextMenu;
+use crate::view::list::palette::{Direction as LimitDirection, PaletteCommand, Sources, Target};
+use crate::view::list::prompt::{Prompt, PromptKind};
+use crate::view::list::query::Query;
+use crate::view::list::search::{Search, fuzzy_match, highlight};
+use crate::view::list::sort::Sort;
+use crate::view::list::table::{TableHeader, TableRow};
+use crate::view::torrent::TorrentView;
+
+struct TorrentItem {
+    torrent: Torrent,
+    /// Positions of the characters of the name matching the search.
+    highlights: Vec<usize>,
+    /// Whether the torrent is targeted by the bulk actions.
+    marked: bool,
+}
+
+impl TorrentItem {
+    fn new(torrent: Torrent) -> Self {
+        Self {
+            torrent,
+            highlights: Vec::new(),
+            marked: false,
+        }
+    }
+
+    /// Spans of the name, prefixed by a marker when the torrent is marked.
+    fn name(&self) -> Vec<Span<'_>> {
+        let name = self.torrent.name.as_deref().unwrap_or_default();
+        let mut spans = highlight(name, &self.highlights);
+        if self.marked {
+            spans.insert(0, MARKER.yellow());
+        }
+        spans
+    }
+}
+
+impl Widget for &TorrentItem {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let [name_area, status, progress, info] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .areas(area);
+
+        // filename
+        Line::from(self.name()).bold().render(name_area, buf);
+
+        // status
+        let text = match self.torrent.status.unwrap_or(TorrentStatus::Stopped) {
+            TorrentStatus::Downloading => {
+                let sending_peers = self.torrent.peers_sending_to_us.unwrap_or(0);
+                let connected_peers = self.torrent.peers_connected.unwrap_or(0);
+                let download_spped = self.torrent.rate_download.unwrap_or(0) as f64;
+                let download_speed = SIZE_FORMATTER.format(download_spped).to_string();
+                let upload_speed = self.torrent.rate_upload.unwrap_or(0) as f64;
+                let upload_speed = SPEED_FORMATTER.format(upload_speed).to_string();
+                Text::from(format!(
+                    "{} from {sending_peers} of {connected_peers} peers - 🔻 {download_speed} / 🔺 {upload_speed}",
+                    torrent_status_label(TorrentStatus::Downloading)
+                ))
+                .fg(Color::Gray)
+            }
+            TorrentStatus::Seeding => {
+                let receiving_peers = self.torrent.peers_getting_from_us.unwrap_or(0);
+                let connected_peers = self.torrent.peers_connected.unwrap_or(0);
+                let upload_speed = self.torrent.rate_upload.unwrap_or(0) as f64;
+                let upload_speed = SPEED_FORMATTER.format(upload_speed).to_string();
+                Text::from(format!(
+                    "{} to {receiving_peers} of {connected_peers} peers - 🔺 {upload_speed}",
+                    torrent_status_label(TorrentStatus::Seeding)
+                ))
+                .fg(Color::LightGreen)
+            }
+            other => Text::from(torrent_status_label(other)),
+        };
+        text.render(status, buf);
+
+        // progress
+        LineGauge::default()
+            .ratio(self.torrent.percent_done.unwrap_or(0.0) as f64)
+            .line_set(THICK)
+            .filled_style(Style::new().green().on_black().bold())
+            .render(progress, buf);
+
+        // info
+        let downloaded_size = self.torrent.downloaded_ever.unwrap_or(0) as f64;
+        let downloaded_size = SIZE_FORMATTER.format(downloaded_size).to_string();
+        let total_size = self.torrent.total_size.unwrap_or(0) as f64;
+        let total_size = SIZE_FORMATTER.format(total_size).to_string();
+        Text::from(format!("{downloaded_size} of {total_size}"))
+            .fg(Color::Gray)
+            .render(info, buf);
+    }
+}
+
+impl crate::components::list::ListItem for &TorrentItem {
+    fn height(&self) -> u16 {
+        4
+    }
+}
+
+mod dialogs;
+mod filter;
+mod group;
+mod menu;
+mod palette;
+mod prompt;
+mod query;
+mod search;
+mod sort;
+mod table;
+
+const HINTS: [Command; 21] = [
+    Command::Quit,
+    Command::Help,
+    Command::Reload,
+    Command::AddTorrent,
+    Command::Open,
+    Command::Menu,
+    Command::ToggleMark,
+    Command::VisualMark,
+    Command::Start,
+    Command::Pause,
+    Command::Verify,
+    Command::Move,
+    Command::Labels,
+    Command::Delete,
+    Command::ToggleSplit,
+    Command::NextSort,
+    Command::NextFilter,
+    Command::Search,
+    Command::Query,
+    Command::ToggleLayout,
+    Command::NextGroup,
+];
+
+const MARKER: &str = "● ";
+
+/// Below this width, the split layout only shows the focused pane.
+const SPLIT_MIN_WIDTH: u16 = 120;
+
+/// Modal above the list, receiving every input until it is closed.
+enum ListOverlay {
+    /// Typing of the search, which stays applied once submitted.
+    Search,
+    Prompt(Prompt),
+    Menu(ContextMenu),
+    Dialog(ListDialog, Dialog),
+}
+
+/// Identifies a row independently of its position, to find it back after a rebuild.
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum RowKey {
+    Group(String),
+    Torrent(i64),
+}
+
+#[derive(Default)]
+pub(super) struct ListView {
+    error: Option<String>,
+    loading: bool,
+    /// Every torrent, as received from the daemon.
+    torrents: Vec<Torrent>,
+    /// Torrents matching the filter, in the sort order.
+    items: Vec<TorrentItem>,
+    group_by: GroupBy,
+    groups: Vec<Group>,
+    /// Keys of the groups collapsed by the user, kept between two refreshes.
+    collapsed: HashSet<String>,
+    /// Visible rows, the selection of the list being an index in there.
+    rows: Vec<Row>,
+    state: ListState,
+    layout: ListLayout,
+    columns: Vec<ColumnConfig>,
+    sort: Sort,
+    filter: StatusFilter,
+    search: Option<Search>,
+    query: Option<Query>,
+    /// Torrents targeted by the bulk actions, kept between two refreshes.
+    marked: HashSet<i64>,
+    /// Row where the visual range started, the range ending at the selection.
+    visual_anchor: Option<RowKey>,
+    /// Values submitted in the add and move dialogs, offered again in their history.
+    add_history: Vec<String>,
+    move_history: Vec<String>,
+    /// Texts submitted in the prompts, offered again in the history of their kind.
+    prompt_history: HashMap<PromptKind, Vec<String>>,
+    /// Overlays above the list, the last one receiving the input until it is closed.
+    overlays: Vec<ListOverlay>,
+    /// Areas of the list, of its tabs and of the detail pane during the last render, to handle
+    /// the clicks.
+    area: Rect,
+    tabs_area: Rect,
+    detail_area: Option<Rect>,
+    clicks: ClickTracker,
+    /// Detail pane of the selected torrent, when the split layout is enabled.
+    detail: Option<Box<TorrentView>>,
+    detail_focused: bool,
+    //
+    chord: Chord,
+    /// Keys pressed in the context menu, looked up in the list bindings to run its entries.
+    shortcut_chord: Chord,
+    subtitle: Subtitle,
+}
+
+impl ListView {
+    fn handle_press_up(&mut self) {
+        self.state.select_previous(self.rows.len());
+    }
+
+    fn handle_press_down(&mut self) {
+        self.state.select_next(self.rows.len());
+    }
+
+    fn handle_press_enter(&mut self, context: &crate::Context) {
+        if let Some(Row::Header(index)) = self.selected_row() {
+            self.toggle_group(index);
+            return;
+        }
+        if let Some(torrent_id) = self.selected_id() {
+            // in the order of the screen, which differs from the sort order when grouped
+            let siblings = self
+                .rows
+                .iter()
+                .filter_map(|row| match row {
+                    Row::Item(index) => self.items.get(*index),
+                    Row::Header(_) => None,
+                })
+                .filter_map(|item| item.torrent.id)
+                .collect();
+            context.send_event(crate::Event::OpenTorrent(torrent_id, siblings));
+        }
+    }
+
+    fn selected_row(&self) -> Option<Row> {
+        self.state
+            .selected()
+            .and_then(|index| self.rows.get(index))
+            .copied()
+    }
+
+    fn selected_item(&self) -> Option<&TorrentItem> {
+        match self.selected_row()? {
+            Row::Item(index) => self.items.get(index),
+            Row::Header(_) => None,
+        }
+    }
+
+    /// Index of the row displaying the given item, if visible.
+    fn item_row(&self, index: usize) -> Option<usize> {
+        self.rows.iter().position(|row| *row == Row::Item(index))
+    }
+
+    /// Selects the first row when nothing is selected anymore.
+    fn reset_selection(&mut self) {
+        self.state.select((!self.rows.is_empty()).then_some(0));
+    }
+
+    fn rebuild_rows(&mut self) {
+        (self.groups, self.rows) = group::build(self.group_by, &self.items, &self.collapsed);
+    }
+
+    fn toggle_group(&mut self, index: usize) {
+        let Some(group) = self.groups.get(index) else {
+            return;
+        };
+        if !self.collapsed.remove(&group.key) {
+            self.collapsed.insert(group.key.clone());
+        }
+        self.rebuild_rows();
+        let row = self.rows.iter().position(|row| *row == Row::Header(index));
+        self.state.select(row);
+    }
+
+    fn set_group_by(&mut self, group_by: GroupBy, context: &crate::Context) {
+        self.group_by = group_by;
+        self.rebuild_items();
+        self.sync_detail(context);
+    }
+
+    /// Filters and sorts the torrents, keeping the selected row, or its nearest neighbor,
+    /// selected.
+    fn rebuild_items(&mut self) {
+        let previous = self
+            .rows
+            .iter()
+            .map(|row| self.row_key(*row))
+            .collect::<Vec<_>>();
+        let selected = self
+            .state
+            .selected()
+            .filter(|index| *index < previous.len());
+        self.items = self
+            .torrents
+            .iter()
+            .filter(|item| self.filter.matches(item))
+            .filter(|item| self.query.as_ref().is_none_or(|query| query.matches(item)))
+            .cloned()
+            .map(TorrentItem::new)
+            .collect();
+        self.items
+            .sort_by(|left, right| self.sort.compare(&left.torrent, &right.torrent));
+        self.rebuild_rows();
+        self.restore_selection(&previous, selected);
+        self.apply_search();
+        self.apply_marks();
+    }
+
+    fn row_key(&self, row: Row) -> Option<RowKey> {
+        match row {
+            Row::Header(index) => self
+                .groups
+                .get(index)
+                .map(|group| RowKey::Group(group.key.clone())),
+            Row::Item(index) => self
+                .items
+                .get(index)
+                .and_then(|item| item.torrent.id)
+                .map(RowKey::Torrent),
+        }
+    }
+
+    fn find_row(&self, key: &RowKey) -> Option<usize> {
+        self.rows
+            .iter()
+            .position(|row| self.row_key(*row).as_ref() == Some(key))
+    }
+
+    /// Selects the row that was selected before the rebuild, or its nearest neighbor still
+    /// visible, looking at the next rows before the previous ones.
+    fn restore_selection(&mut self, previous: &[Option<RowKey>], selected: Option<usize>) {
+        let Some(selected) = selected else {
+            self.reset_selection();
+            return;
+        };
+        let mut positions = HashMap::with_capacity(self.rows.len());
+        for (position, row) in self.rows.iter().enumerate() {
+            if let Some(key) = self.row_key(*row) {
+                positions.entry(key).or_insert(position);
+            }
+        }
+        let found = std::iter::once(Some(selected))
+            .chain((1..previous.len()).flat_map(|distance| {
+                [
+                    selected.checked_add(distance),
+                    selected.checked_sub(distance),
+                ]
+            }))
+            .flatten()
+            .filter_map(|index| previous.get(index).and_then(Option::as_ref))
+            .find_map(|key| positions.get(key).copied());
+        match found {
+            Some(row) => self.state.select(Some(row)),
+            None => self.reset_selection(),
+        }
+    }
+
+    /// Highlights the characters matching the search, and returns the index of the best match.
+    fn apply_search(&mut self) -> Option<usize> {
+        let query = self
+            .search
+            .as_ref()
+            .map(|search| search.query.as_str())
+            .unwrap_or_default();
+        let mut best: Option<(i64, usize)> = None;
+        for (index, item) in self.items.iter_mut().enumerate() {
+            let found = Some(query)
+                .filter(|query| !query.is_empty())
+                .zip(item.torrent.name.as_deref())
+                .and_then(|(query, name)| fuzzy_match(query, name));
+            item.highlights = match found {
+                Some((score, positions)) => {
+                    if best.is_none_or(|(best_score, _)| score > best_score) {
+                        best = Some((score, index));
+                    }
+                    positions
+                }
+                None => Vec::new(),
+            };
+        }
+        best.map(|(_, index)| index)
+    }
+
+    /// Edits the prompt, and returns whether it stays open.
+    fn handle_prompt_input(
+        &mut self,
+        prompt: &mut Prompt,
+        key: KeyEvent,
+        context: &crate::Context,
+    ) -> bool {
+        match key.code {
+            KeyCode::Enter => {
+                let text = prompt.input.value().trim().to_string();
+                match self.submit_prompt(prompt.kind, &text, context) {
+                    Ok(()) => {
+                        if !text.is_empty() {
+                            remember(self.prompt_history.entry(prompt.kind).or_default(), &text);
+                        }
+                        return false;
+                    }
+                    Err(err) => prompt.error = Some(err),
+                }
+            }
+            KeyCode::Esc => return false,
+            KeyCode::Tab => self.complete_prompt(prompt, context),
+            KeyCode::Backspace if prompt.input.value().is_empty() => return false,
+            KeyCode::Up => {
+                prompt.input.history_previous();
+                prompt.candidates.clear();
+            }
+            KeyCode::Down => {
+                prompt.input.history_next();
+                prompt.candidates.clear();
+            }
+            _ => {
+                if prompt.input.handle_key(key) {
+                    prompt.error = None;
+                    prompt.candidates.clear();
+                }
+            }
+        }
+        true
+    }
+
+    fn submit_prompt(
+        &mut self,
+        kind: PromptKind,
+        text: &str,
+        context: &crate::Context,
+    ) -> Result<(), String> {
+        match kind {
+            PromptKind::Query => {
+                self.query = Query::parse(text).map_err(|err| err.to_string())?;
+                self.rebuild_items();
+                self.sync_detail(context);
+            }
+            PromptKind::Mark => {
+                if let Some(query) = Query::parse(text).map_err(|err| err.to_string())? {
+                    let ids = self
+                        .items
+                        .iter()
+                        .filter(|item| query.matches(&item.torrent))
+                        .filter_map(|item| item.torrent.id);
+                    self.marked.extend(ids);
+                }
+            }
+            PromptKind::Labels => {
+                let labels = palette::split_labels(text);
+                let ids = self.take_targets();
+                if !ids.is_empty() {
+                    context.send_action(Action::SetTorrentsLabels(ids, labels));
+                }
+            }
+            PromptKind::Command => self.run_palette(text, context)?,
+        }
+        Ok(())
+    }
+
+    /// Labels, directories and trackers of the torrents, and the configured profiles.
+    fn completion_sources(&self, context: &crate::Context) -> Sources {
+        let labels = self
+            .torrents
+            .iter()
+            .flat_map(|torrent| torrent.labels.iter().flatten().cloned())
+            .collect::<BTreeSet<_>>();
+        let directories = self
+            .torrents
+            .iter()
+            .filter_map(|torrent| torrent.download_dir.clone())
+            .collect::<BTreeSet<_>>();
+        let trackers = self
+            .torrents
+            .iter()
+            .flat_map(tracker_hosts)
+            .collect::<BTreeSet<_>>();
+        Sources {
+            labels: labels.into_iter().collect(),
+            directories: directories.into_iter().collect(),
+            trackers: trackers.into_iter().collect(),
+            profiles: context.config().profiles.keys().cloned().collect(),
+        }
+    }
+
+    fn complete_prompt(&self, prompt: &mut Prompt, context: &crate::Context) {
+        let sources = self.completion_sources(context);
+        let text = prompt.input.value();
+        let completion = match prompt.kind {
+            PromptKind::Command => palette::complete(text, &sources),
+            PromptKind::Query | PromptKind::Mark => {
+                complete_with(text, Argument::Words, &palette::query_candidates(&sources))
+            }
+            PromptKind::Labels => complete_with(text, Argument::List, &sources.labels),
+        };
+        prompt.input.set_value(completion.text);
+        prompt.candidates = completion.candidates;
+        prompt.error = None;
+    }
+
+    /// Ids of the torrents targeted by a command of the palette.
+    fn palette_targets(&mut self, target: Target) -> Vec<i64> {
+        match target {
+            Target::Selection => self.take_targets(),
+            Target::All => self
+                .items
+                .iter()
+                .filter_map(|item| item.torrent.id)
+                .collect(),
+        }
+    }
+
+    fn run_palette(&mut self, text: &str, context: &crate::Context) -> Result<(), String> {
+        let send = |action: Action| context.send_action(action);
+        match palette::parse(text)? {
+            PaletteCommand::Add(filename) => send(Action::AddTorrent(TorrentAddArgs {
+                filename: Some(filename),
+                ..Default::default()
+            })),
+            PaletteCommand::Start(target) => {
+                let ids = self.palette_targets(target);
+                if !ids.is_empty() {
+                    send(Action::StartTorrents(ids));
+                }
+            }
+            PaletteCommand::Stop(target) => {
+                let ids = self.palette_targets(target);
+                if !ids.is_empty() {
+                    send(Action::StopTorrents(ids));
+                }
+            }
+            PaletteCommand::Verify(target) => {
+                let ids = self.palette_targets(target);
+                if !ids.is_empty() {
+                    send(Action::VerifyTorrents(ids));
+                }
+            }
+            PaletteCommand::Delete(target) => {
+                let ids = self.palette_targets(target);
+                self.open_delete_dialog(ids, context);
+            }
+            PaletteCommand::Filter(query) => {
+                self.query = Query::parse(&query).map_err(|err| err.to_string())?;
+                self.rebuild_items();
+                self.sync_detail(context);
+            }
+            PaletteCommand::Mark(query) => {
+                if let Some(query) = Query::parse(&query).map_err(|err| err.to_string())? {
+                    let ids = self
+                        .items
+                        .iter()
+                        .filter(|item| query.matches(&item.torrent))
+                        .filter_map(|item| item.torrent.id);
+                    self.marked.extend(ids);
+                }
+            }
+            PaletteCommand::Sort(field, descending) => {
+                let descending = descending.unwrap_or(self.sort.descending());
+                self.set_sort(Sort::new(field, descending), context);
+            }
+            PaletteCommand::Limit(LimitDirection::Download, limit) => {
+                send(Action::SetDownloadLimit(limit))
+            }
+            PaletteCommand::Limit(LimitDirection::Upload, limit) => {
+                send(Action::SetUploadLimit(limit))
+            }
+            PaletteCommand::Profile(name) => {
+                let profile = context
+                    .config()
+                    .profiles
+                    .get(&name)
+                    .ok_or_else(|| format!("unknown profile {name:?}"))?;
+                self.torrents.clear();
+                self.marked.clear();
+                self.rebuild_items();
+                send(Action::Connect(profile.clone()));
+            }
+            PaletteCommand::Move(location, move_data) => {
+                let ids = self.take_targets();
+                if !ids.is_empty() {
+                    send(Action::MoveTorrents(ids, location, move_data));
+                }
+            }
+            PaletteCommand::Label(labels) => {
+                let ids = self.take_targets();
+                if !ids.is_empty() {
+                    send(Action::SetTorrentsLabels(ids, labels));
+                }
+            }
+            PaletteCommand::Run(command) => self.run_command(command, context),
+        }
+        Ok(())
+    }
+
+    fn open_prompt(&mut self, kind: PromptKind) {
+        let selected = self.selected_item().map(|item| &item.torrent);
+        let text = match kind {
+            PromptKind::Query => self
+                .query
+                .as_ref()
+                .map(|query| query.source().to_string())
+                .unwrap_or_default(),
+            PromptKind::Mark | PromptKind::Command => String::new(),
+            PromptKind::Labels => selected
+                .and_then(|torrent| torrent.labels.as_ref())
+                .map(|labels| labels.join(", "))
+                .unwrap_or_default(),
+        };
+        let history = self.prompt_history.get(&kind).cloned().unwrap_or_default();
+        self.overlays
+            .push(ListOverlay::Prompt(Prompt::new(kind, text, history)));
+    }
+
+    /// Identifiers of the torrents displayed by a row, every torrent of the group for a header.
+    fn row_ids(&self, row: Row) -> Vec<i64> {
+        let indexes = match row {
+            Row::Item(index) => vec![index],
+            Row::Header(index) => self
+                .groups
+                .get(index)
+                .map(|group| group.items.clone())
+                .unwrap_or_default(),
+        };
+        indexes
+            .into_iter()
+            .filter_map(|index| self.items.get(index).and_then(|item| item.torrent.id))
+            .collect()
+    }
+
+    /// Torrents between the start of the visual range and the selection.
+    fn visual_ids(&self) -> Vec<i64> {
+        let anchor = self
+            .visual_anchor
+            .as_ref()
+            .and_then(|key| self.find_row(key));
+        let Some((anchor, selected)) = anchor.zip(self.state.selected()) else {
+            return Vec::new();
+        };
+        let range = anchor.min(selected)..=anchor.max(selected);
+        self.rows
+            .get(range)
+            .unwrap_or_default()
+            .iter()
+            .flat_map(|row| self.row_ids(*row))
+            .collect()
+    }
+
+    /// Marked torrents, including the ones of the visual range.
+    fn marked_ids(&self) -> HashSet<i64> {
+        let mut marked = self.marked.clone();
+        marked.extend(self.visual_ids());
+        marked
+    }
+
+    /// Flags the marked items, for them to be rendered with a marker.
+    fn apply_marks(&mut self) {
+        let marked = self.marked_ids();
+        for item in &mut self.items {
+            item.marked = item.torrent.id.is_some_and(|id| marked.contains(&id));
+        }
+    }
+
+    /// Torrents targeted by an action: the marked ones, or else the selected one. Ends the
+    /// visual range.
+    fn take_targets(&mut self) -> Vec<i64> {
+        let mut targets = self.marked_ids().into_iter().collect::<Vec<_>>();
+        self.visual_anchor = None;
+        if targets.is_empty() {
+            return self.selected_id().into_iter().collect();
+        }
+        targets.sort_unstable();
+        targets
+    }
+
+    fn toggle_mark(&mut self, context: &crate::Context) {
+        let Some(row) = self.selected_row() else {
+            return;
+        };
+        let ids = self.row_ids(row);
+        if ids.iter().all(|id| self.marked.contains(id)) {
+            ids.iter().for_each(|id| {
+                self.marked.remove(id);
+            });
+        } else {
+            self.marked.extend(ids);
+        }
+        self.handle_press_down();
+        self.sync_detail(context);
+    }
+
+    fn toggle_visual(&mut self) {
+        if self.visual_anchor.is_some() {
+            self.marked.extend(self.visual_ids());
+            self.visual_anchor = None;
+        } else {
+            self.visual_anchor = self.selected_row().and_then(|row| self.row_key(row));
+        }
+    }
+
+    /// Marks every visible torrent, or unmarks them when they are all marked already.
+    fn toggle_mark_all(&mut self) {
+        let ids = self
+            .items
+            .iter()
+            .filter_map(|item| item.torrent.id)
+            .collect::<Vec<_>>();
+        if ids.iter().all(|id| self.marked.contains(id)) {
+            ids.iter().for_each(|id| {
+                self.marked.remove(id);
+            });
+        } else {
+            self.marked.extend(ids);
+        }
+    }
+
+    /// Torrents the context menu applies to: the marked ones, or else the selected one.
+    fn menu_torrents(&self) -> Vec<&Torrent> {
+        let marked = self.marked_ids();
+        if marked.is_empty() {
+            return self
+                .selected_item()
+                .map(|item| &item.torrent)
+                .into_iter()
+                .collect();
+        }
+        self.torrents
+            .iter()
+            .filter(|torrent| torrent.id.is_some_and(|id| marked.contains(&id)))
+            .collect()
+    }
+
+    fn open_menu(&mut self, context: &crate::Context) {
+        let torrents = self.menu_torrents();
+        let title = match torrents.as_slice() {
+            [torrent] => format!(" {} ", torrent.name.as_deref().unwrap_or_default()),
+            torrents => format!(" {} marked torrents ", torrents.len()),
+        };
+        let items = menu::COMMANDS
+            .into_iter()
+            .filter(|command| *command != Command::Open || self.selected_item().is_some())
+            .filter(|command| {
+                torrents
+                    .iter()
+                    .any(|torrent| menu::applies_to(*command, torrent))
+            })
+            .map(|command| {
+                let trigger = context.keymap().hint(Scope::List, command);
+                SubtitleItem::new(command, trigger.unwrap_or_default())
+            })
+            .collect::<Vec<_>>();
+        if !items.is_empty() {
+            self.chord.clear();
+            self.overlays.push(ListOverlay::Menu(ContextMenu {
+                title,
+                items,
+                selected: 0,
+            }));
+        }
+    }
+
+    /// Moves in the menu or runs one of its entries, and returns whether it stays open.
+    fn handle_menu_input(
+        &mut self,
+        menu: &mut ContextMenu,
+        key: KeyEvent,
+        context: &crate::Context,
+    ) -> bool {
+        match self.chord.resolve(context.keymap(), Scope::Dialog, key) {
+            Resolution::Command(Command::Up) => menu.select_previous(),
+            Resolution::Command(Command::Down) => menu.select_next(),
+            Resolution::Command(Command::Select) => {
+                if let Some(command) = menu.selected_command() {
+                    self.run_command(command, context);
+                }
+                return false;
+            }
+            Resolution::Command(Command::Close) => return false,
+            Resolution::Pending => {}
+            // the shortcuts of the list run the entries directly
+            _ => {
+                if let Resolution::Command(command) =
+                    self.shortcut_chord
+                        .resolve(context.keymap(), Scope::List, key)
+                    && menu.items.iter().any(|item| item.command() == command)
+                {
+                    self.run_command(command, context);
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    fn handle_menu_mouse(
+        &mut self,
+        menu: &mut ContextMenu,
+        event: MouseEvent,
+        context: &crate::Context,
+    ) -> bool {
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let position = Position::new(event.column, event.row);
+                let clicked = menu
+                    .widget()
+                    .item_at(self.area, position)
+                    .and_then(|index| menu.items.get(index))
+                    .map(SubtitleItem::command);
+                if let Some(command) = clicked {
+                    self.run_command(command, context);
+                }
+                return false;
+            }
+            MouseEventKind::ScrollUp => menu.select_previous(),
+            MouseEventKind::ScrollDown => menu.select_next(),
+            _ => {}
+        }
+        true
+    }
+
+    /// Torrents of the list having one of the ids.
+    fn torrents_with_ids(&self, ids: &[i64]) -> Vec<&Torrent> {
+        self.torrents
+            .iter()
+            .filter(|torrent| torrent.id.is_some_and(|id| ids.contains(&id)))
+            .collect()
+    }
+
+    fn open_delete_dialog(&mut self, ids: Vec<i64>, context: &crate::Context) {
+        if ids.is_empty() {
+            return;
+        }
+        let dialog = dialogs::delete(context.keymap(), &self.torrents_with_ids(&ids));
+        self.overlays
+            .push(ListOverlay::Dialog(ListDialog::Delete(ids), dialog));
+    }
+
+    fn open_move_dialog(&mut self, context: &crate::Context) {
+        let ids = self.take_targets();
+        if ids.is_empty() {
+            return;
+        }
+        let directories = self.completion_sources(context).directories;
+        let dialog = dialogs::move_to(
+            context.keymap(),
+            &self.torrents_with_ids(&ids),
+            &self.move_history,
+            directories,
+        );
+        self.overlays
+            .push(ListOverlay::Dialog(ListDialog::Move(ids), dialog));
+    }
+
+    fn open_add_dialog(&mut self, context: &crate::Context) {
+        let directories = self.completion_sources(context).directories;
+        let dialog = dialogs::add(context.keymap(), &self.add_history, directories);
+        self.overlays
+            .push(ListOverlay::Dialog(ListDialog::Add, dialog));
+    }
+
+    /// Runs the command chosen in the dialog, and returns whether it stays open, like when its
+    /// values are rejected.
+    fn handle_dialog_command(
+        &mut self,
+        kind: &mut ListDialog,
+        dialog: &mut Dialog,
+        command: Command,
+        context: &crate::Context,
+    ) -> bool {
+        let done = match (kind, command) {
+            (_, Command::Close) => true,
+            (ListDialog::Delete(ids), Command::Yes | Command::No) => {
+                let ids = std::mem::take(ids);
+                context.send_action(Action::DeleteTorrents(ids, command == Command::Yes));
+                true
+            }
+            (ListDialog::Move(ids), Command::Select) => {
+                let location = dialog.text(dialogs::DIRECTORY).trim().to_string();
+                let move_data = dialog.checked(dialogs::MOVE_DATA);
+                remember(&mut self.move_history, &location);
+                let ids = std::mem::take(ids);
+                context.send_action(Action::MoveTorrents(ids, location, move_data));
+                true
+            }
+            (ListDialog::Add, Command::Select) => {
+                let filename = dialog.text(dialogs::TORRENT).trim().to_string();
+                let directory = dialog.text(dialogs::DIRECTORY).trim().to_string();
+                remember(&mut self.add_history, &filename);
+                context.send_action(Action::AddTorrent(TorrentAddArgs {
+                    filename: Some(filename),
+                    download_dir: Some(directory).filter(|directory| !directory.is_empty()),
+                    paused: Some(dialog.checked(dialogs::PAUSED)),
+                    bandwidth_priority: dialogs::PRIORITIES
+                        .get(dialog.selected(dialogs::PRIORITY))
+                        .copied(),
+                    ..Default::default()
+                }));
+                true
+            }
+            (ListDialog::Limits, Command::Select) => {
+                let download =
+                    dialogs::chosen_limit(dialog, dialogs::DOWNLOAD, dialogs::DOWNLOAD_SPEED);
+                let upload = download.as_ref().and_then(|_| {
+                    dialogs::chosen_limit(dialog, dialogs::UPLOAD, dialogs::UPLOAD_SPEED)
+                });
+                if let Some((download, upload)) = download.zip(upload) {
+                    for (choice, action) in [
+                        (
+                            download,
+                            Action::SetDownloadLimit as fn(Option<i32>) -> Action,
+                        ),
+                        (upload, Action::SetUploadLimit),
+                    ] {
+                        match choice {
+                            LimitChoice::Unchanged => {}
+                            LimitChoice::Unlimited => context.send_action(action(None)),
+                            LimitChoice::Limited(limit) => context.send_action(action(Some(limit))),
+                        }
+                    }
+                    true
+                } else {
+                    false
+                }
+            }
+            _ => false,
+        };
+        !done
+    }
+
+    fn run_command(&mut self, command: Command, context: &crate::Context) {
+        match command {
+            Command::Quit => context.send_event(crate::Event::Quit),
+            Command::Help => context.send_event(crate::Event::OpenHelp(Scope::List)),
+            Command::Log => context.send_event(crate::Event::OpenLog),
+            Command::Inspector => context.send_event(crate::Event::OpenInspector(Scope::List)),
+            Command::Back => self.go_back(context),
+            Command::Reload => context.send_action(Action::RefreshList),
+            Command::Open => self.handle_press_enter(context),
+            Command::Menu => self.open_menu(context),
+            Command::Palette => self.open_prompt(PromptKind::Command),
+            Command::Up => self.handle_press_up(),
+            Command::Down => self.handle_press_down(),
+            Command::PageUp => self.state.select_previous_page(self.rows.len()),
+            Command::PageDown => self.state.select_next_page(self.rows.len()),
+            Command::First => self.state.select_first(self.rows.len()),
+            Command::Last => self.state.select_last(self.rows.len()),
+            Command::FocusDetail if self.detail.is_some() => self.detail_focused = true,
+            Command::ToggleSplit => self.toggle_split(context),
+            Command::ToggleLayout => self.toggle_layout(context),
+            Command::NextGroup => self.set_group_by(self.group_by.next(), context),
+            Command::NextSort => self.set_sort(self.sort.with_next_field(), context),
+            Command::ToggleSortDirection => {
+                self.set_sort(self.sort.with_toggled_direction(), context);
+            }
+            Command::NextFilter => self.set_filter(self.filter.next(), context),
+            Command::PreviousFilter => self.set_filter(self.filter.previous(), context),
+            Command::Search => {
+                self.search = Some(Search::default());
+                self.overlays.push(ListOverlay::Search);
+                self.apply_search();
+            }
+            Command::NextMatch => self.jump_to_match(true, context),
+            Command::PreviousMatch => self.jump_to_match(false, context),
+            Command::Query => self.open_prompt(PromptKind::Query),
+            Command::MarkByQuery => self.open_prompt(PromptKind::Mark),
+            Command::Move => self.open_move_dialog(context),
+            Command::AddTorrent => self.open_add_dialog(context),
+            Command::SpeedLimits => {
+                self.overlays.push(ListOverlay::Dialog(
+                    ListDialog::Limits,
+                    dialogs::limits(context.keymap()),
+                ));
+            }
+            Command::Labels => self.open_prompt(PromptKind::Labels),
+            Command::ToggleMark => self.toggle_mark(context),
+            Command::VisualMark => self.toggle_visual(),
+            Command::MarkAll => self.toggle_mark_all(),
+            Command::ClearMarks => {
+                self.marked.clear();
+                self.visual_anchor = None;
+            }
+            Command::Start => self.send_targets(Action::StartTorrents, context),
+            Command::Pause => self.send_targets(Action::StopTorrents, context),
+            Command::Verify => self.send_targets(Action::VerifyTorrents, context),
+            Command::Delete => {
+                self.chord.clear();
+                let ids = self.take_targets();
+                self.open_delete_dialog(ids, context);
+            }
+            _ => {}
+        }
+        if matches!(
+            command,
+            Command::Up
+                | Command::Down
+                | Command::PageUp
+                | Command::PageDown
+                | Command::First
+                | Command::Last
+        ) {
+            self.sync_detail(context);
+        }
+    }
+
+    /// Ends the visual range, or else clears the search, or else the query.
+    fn go_back(&mut self, context: &crate::Context) {
+        if self.visual_anchor.take().is_some() {
+            return;
+        }
+        if self.search.take().is_some() {
+            self.apply_search();
+        } else if self.query.take().is_some() {
+            self.rebuild_items();
+            self.sync_detail(context);
+        }
+    }
+
+    fn send_targets(&mut self, action: fn(Vec<i64>) -> Action, context: &crate::Context) {
+        let ids = self.take_targets();
+        if !ids.is_empty() {
+            context.send_action(action(ids));
+        }
+    }
+
+    /// Edits the search, and returns whether it is still being typed.
+    fn handle_search_input(&mut self, code: KeyCode, context: &crate::Context) -> bool {
+        let Some(search) = self.search.as_mut() else {
+            return false;
+        };
+        match code {
+            KeyCode::Enter => return false,
+            KeyCode::Esc => {
+                self.search = None;
+                self.apply_search();
+                return false;
+            }
+            KeyCode::Backspace if search.query.is_empty() => {
+                self.search = None;
+                self.apply_search();
+                return false;
+            }
+            KeyCode::Backspace => {
+                search.query.pop();
+                self.jump_to_best_match(context);
+            }
+            KeyCode::Char(c) => {
+                search.query.push(c);
+                self.jump_to_best_match(context);
+            }
+            _ => {}
+        }
+        true
+    }
+
+    /// Gives the input to the top overlay, which is put back at its place unless it got closed.
+    fn update_overlay(&mut self, event: Event, context: &crate::Context) {
+        let Some(mut overlay) = self.overlays.pop() else {
+            return;
+        };
+        let index = self.overlays.len();
+        let keep = match (&mut overlay, event) {
+            (ListOverlay::Search, Event::Key(key)) => self.handle_search_input(key.code, context),
+            (ListOverlay::Prompt(prompt), Event::Key(key)) => {
+                self.handle_prompt_input(prompt, key, context)
+            }
+            (ListOverlay::Menu(menu), Event::Key(key)) => {
+                self.handle_menu_input(menu, key, context)
+            }
+            (ListOverlay::Menu(menu), Event::Mouse(mouse)) => {
+                self.handle_menu_mouse(menu, mouse, context)
+            }
+            (ListOverlay::Dialog(kind, dialog), event) => {
+                match dialog.update(context.keymap(), self.area, &event) {
+                    Some(command) => self.handle_dialog_command(kind, dialog, command, context),
+                    None => true,
+                }
+            }
+            // the inputs at the bottom of the list only use the keyboard
+            (ListOverlay::Search | ListOverlay::Prompt(_) | ListOverlay::Menu(_), _) => true,
+        };
+        if keep {
+            // the input may have opened another overlay, which goes above this one
+            self.overlays
+                .insert(index.min(self.overlays.len()), overlay);
+        }
+    }
+
+    fn jump_to_best_match(&mut self, context: &crate::Context) {
+        if let Some(row) = self.apply_search().and_then(|index| self.item_row(index)) {
+            self.state.select(Some(row));
+            self.sync_detail(context);
+        }
+    }
+
+    /// Selects the next (or previous) visible item matching the search, wrapping around the list.
+    fn jump_to_match(&mut self, forward: bool, context: &crate::Context) {
+        if self.search.is_none() {
+            return;
+        }
+        let len = self.rows.len();
+        let current = self.state.selected().unwrap_or(0);
+        let found = (1..=len)
+            .map(|step| {
+                if forward {
+                    (current + step) % len
+                } else {
+                    (current + len - step) % len
+                }
+            })
+            .find(|index| match self.rows[*index] {
+                Row::Item(item) => !self.items[item].highlights.is_empty(),
+                Row::Header(_) => false,
+            });
+        if let Some(index) = found {
+            self.state.select(Some(index));
+            self.sync_detail(context);
+        }
+    }
+
+    fn filter_labels(&self) -> Vec<String> {
+        StatusFilter::ALL
+            .iter()
+            .map(|filter| {
+                let count = self
+                    .torrents
+                    .iter()
+                    .filter(|item| filter.matches(item))
+                    .count();
+                format!("{} ({count})", filter.label())
+            })
+            .collect()
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent, context: &crate::Context) {
+        let position = Position::new(event.column, event.row);
+        let pressed = matches!(event.kind, MouseEventKind::Down(_));
+        if self.detail_area.is_some_and(|area| area.contains(position)) {
+            self.detail_focused |= pressed;
+            self.update_detail(crate::Event::InputEvent(Event::Mouse(event)), context);
+            return;
+        }
+        self.detail_focused &= !pressed;
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => self.handle_click(position, context),
+            MouseEventKind::Down(MouseButton::Right) => {
+                if let Some(row) = self.state.index_at(position) {
+                    self.state.select(Some(row));
+                    self.sync_detail(context);
+                }
+                self.open_menu(context);
+            }
+            MouseEventKind::ScrollUp if self.state.contains(position) => {
+                self.handle_press_up();
+                self.sync_detail(context);
+            }
+            MouseEventKind::ScrollDown if self.state.contains(position) => {
+                self.handle_press_down();
+                self.sync_detail(context);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_click(&mut self, position: Position, context: &crate::Context) {
+        let labels = self.filter_labels();
+        if let Some(index) = tab_at(labels.iter().map(String::as_str), self.tabs_area, position) {
+            self.set_filter(StatusFilter::ALL[index], context);
+        } else if let Some(row) = self.state.index_at(position) {
+            self.state.select(Some(row));
+            self.sync_detail(context);
+            if self.clicks.click(row) {
+                self.handle_press_enter(context);
+            }
+        } else if let Some(command) = self.subtitle.command_at(self.area, position) {
+            self.run_command(command, context);
+        }
+    }
+
+    fn toggle_layout(&mut self, context: &crate::Context) {
+        self.layout = match self.layout {
+            ListLayout::Cards => ListLayout::Table,
+            ListLayout::Table => ListLayout::Cards,
+        };
+        context.send_event(crate::Event::ListLayoutChange(self.layout));
+    }
+
+    fn set_sort(&mut self, sort: Sort, context: &crate::Context) {
+        self.sort = sort;
+        context.send_event(crate::Event::ListSortChange(sort.to_string()));
+        self.rebuild_items();
+        self.sync_detail(context);
+    }
+
+    fn set_filter(&mut self, filter: StatusFilter, context: &crate::Context) {
+        self.filter = filter;
+        self.rebuild_items();
+        self.sync_detail(context);
+    }
+
+    fn selected_id(&self) -> Option<i64> {
+        self.selected_item().and_then(|item| item.torrent.id)
+    }
+
+    fn toggle_split(&mut self, context: &crate::Context) {
+        self.detail_focused = false;
+        if self.detail.take().is_some() {
+            return;
+        }
+        // there is nothing to show in the pane without a selected torrent
+        if let Some(id) = self.selected_id() {
+            let mut detail = TorrentView::new(id, Vec::new());
+            detail.init(context);
+            self.detail = Some(Box::new(detail));
+        }
+    }
+
+    /// Makes the detail pane follow the selection of the list.
+    fn sync_detail(&mut self, context: &crate::Context) {
+        let selected = self.selected_id();
+        if let Some((detail, id)) = self.detail.as_mut().zip(selected)
+            && detail.id() != id
+        {
+            detail.set_torrent(id, context);
+        }
+    }
+
+    fn update_detail(&mut self, event: crate::Event, context: &crate::Context) {
+        let Some(detail) = self.detail.as_mut() else {
+            return;
+        };
+        match event {
+            crate::Event::InputEvent(Event::Key(inner)) => {
+                let Resolution::Command(command) =
+                    self.chord.resolve(context.keymap(), Scope::Detail, inner)
+                else {
+                    return;
+                };
+                match command {
+                    Command::Back => self.detail_focused = false,
+                    Command::ToggleSplit => self.toggle_split(context),
+                    Command::PreviousTorrent => self.run_command(Command::Up, context),
+                    Command::NextTorrent => self.run_command(Command::Down, context),
+                    command => detail.run_command(command, context),
+                }
+            }
+            other => detail.update(other, context),
+        }
+    }
+
+    pub(super) fn init(&mut self, context: &crate::Context) {
+        self.layout = context.config().list.layout;
+        self.sort = Sort::parse(&context.config().list.sort).unwrap_or_default();
+        self.columns = context.config().list.columns.clone();
+        self.subtitle = Subtitle::new(context.keymap(), Scope::List, &HINTS);
+        context.send_action(Action::RefreshList);
+    }
+
+    /// Keeps the selection, offset and items, and only refreshes them in the background.
+    pub(super) fn resume(&mut self, context: &crate::Context) {
+        context.send_action(Action::RefreshList);
+    }
+
+    pub(super) fn update(&mut self, event: crate::Event, context: &crate::Context) {
+        match event {
+            crate::Event::InputEvent(inner) if !self.overlays.is_empty() => {
+                self.update_overlay(inner, context);
+            }
+            crate::Event::InputEvent(Event::Key(_)) if self.detail_focused => {
+                self.update_detail(event, context);
+            }
+            crate::Event::InputEvent(Event::Mouse(inner)) => self.handle_mouse(inner, context),
+            crate::Event::InputEvent(Event::Key(inner)) => {
+                if let Resolution::Command(command) =
+                    self.chord.resolve(context.keymap(), Scope::List, inner)
+                {
+                    self.run_command(command, context);
+                }
+            }
+            crate::Event::TorrentDeleteStart(_) | crate::Event::TorrentChangeStart(_) => {
+                self.error = None;
+                self.loading = true;
+            }
+            crate::Event::TorrentDelete(ids) => {
+                self.error = None;
+                self.torrents
+                    .retain(|item| !item.id.is_some_and(|id| ids.contains(&id)));
+                self.marked.retain(|id| !ids.contains(id));
+                self.loading = false;
+                self.rebuild_items();
+                context.send_action(Action::RefreshList);
+            }
+            // the error is notified by the runner
+            crate::Event::TorrentDeleteError(..) | crate::Event::TorrentChangeError(..) => {
+                self.loading = true;
+                context.send_action(Action::RefreshList);
+            }
+            crate::Event::TorrentChange(ids) => {
+                self.error = None;
+                self.loading = false;
+                // the marks of a bulk action are done with once it succeeded
+                self.marked.retain(|id| !ids.contains(id));
+                context.send_action(Action::RefreshList);
+                if let Some(detail) = self.detail.as_ref()
+                    && ids.contains(&detail.id())
+                {
+                    context.send_action(Action::RefreshTorrent(detail.id()));
+                }
+            }
+            crate::Event::TorrentListUpdateStart => {
+                self.error = None;
+                self.loading = true;
+            }
+            crate::Event::TorrentListUpdate(list) => {
+                self.torrents = list;
+                self.marked
+                    .retain(|id| self.torrents.iter().any(|torrent| torrent.id == Some(*id)));
+                self.loading = false;
+                self.rebuild_items();
+                self.sync_detail(context);
+            }
+            crate::Event::TorrentListUpdateError(err) => {
+                self.loading = false;
+                self.error = Some(err.to_string());
+            }
+            crate::Event::SessionChange => {
+                self.error = None;
+                context.send_action(Action::RefreshList);
+            }
+            event @ (crate::Event::TorrentUpdateStart
+            | crate::Event::TorrentUpdate(_)
+            | crate::Event::TorrentUpdateError(..)) => self.update_detail(event, context),
+            _ => {}
+        }
+        // the marks follow the selection while a visual range is active
+        self.apply_marks();
+    }
+}
+
+impl ListView {
+    pub(super) fn render(&mut self, breadcrumb: &str, area: Rect, buf: &mut Buffer) {
+        self.detail_area = None;
+        let Some(detail) = self.detail.as_mut() else {
+            self.render_list(breadcrumb, area, buf);
+            return;
+        };
+        if area.width < SPLIT_MIN_WIDTH {
+            if self.detail_focused {
+                detail.render(&detail.label(), area, buf);
+                self.detail_area = Some(area);
+            } else {
+                self.render_list(breadcrumb, area, buf);
+            }
+            return;
+        }
+        let [list_area, detail_area] =
+            Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
+                .areas(area);
+        detail.render(&detail.label(), detail_area, buf);
+        self.detail_area = Some(detail_area);
+        self.render_list(breadcrumb, list_area, buf);
+        // dim the pane that doesn't receive the keyboard events
+        let unfocused = if self.detail_focused {
+            list_area
+        } else {
+            detail_area
+        };
+        buf.set_style(unfocused, Style::new().dim());
+    }
+
+    fn render_list(&mut self, breadcrumb: &str, area: Rect, buf: &mut Buffer) {
+        self.area = area;
+        let marked = self.marked_ids();
+        // the marked torrents hidden by the filters are still targeted by the actions
+        let hidden = marked.len() - self.items.iter().filter(|item| item.marked).count();
+        let block = Block::bordered()
+            .title(if self.loading {
+                Title::from(format!(" {breadcrumb} (Loading...) "))
+            } else if let Some(err) = self.error.as_ref() {
+                Title::from(format!(" {breadcrumb} ({err}) ").red())
+            } else {
+                Title::from(format!(" {breadcrumb} "))
+            })
+            .title(
+                Some(marked.len())
+                    .filter(|count| *count > 0)
+                    .map(|count| match hidden {
+                        0 => format!(" {count} marked "),
+                        hidden => format!(" {count} marked ({hidden} hidden) "),
+                    })
+                    .map(|title| Line::from(title).right_aligned())
+                    .unwrap_or_default(),
+            )
+            .title(
+                self.query
+                    .as_ref()
+                    .map(|query| Line::from(format!(" Query: {} ", query.source())).right_aligned())
+                    .unwrap_or_default(),
+            )
+            .title(
+                Some(self.group_by)
+                    .filter(|group_by| *group_by != GroupBy::None)
+                    .map(|group_by| {
+                        Line::from(format!(" Group: {} ", group_by.label())).right_aligned()
+                    })
+                    .unwrap_or_default(),
+            )
+            .title(Line::from(format!(" Sort: {} ", self.sort.label())).right_aligned())
+            .title_bottom(self.subtitle.line());
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let [tabs, _, content] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .areas(inner);
+        self.tabs_area = tabs;
+        Tabs::new(self.filter_labels().into_iter().map(Line::from))
+            .select(self.filter.index())
+            .highlight_style(Style::new().bold().reversed())
+            .render(tabs, buf);
+
+        let mut footer = Vec::new();
+        if let Some(search) = self.search.as_ref() {
+            let matches = self
+                .items
+                .iter()
+                .filter(|item| !item.highlights.is_empty())
+                .count();
+            let editing = self
+                .overlays
+                .iter()
+                .any(|overlay| matches!(overlay, ListOverlay::Search));
+            let cursor = if editing { "█" } else { "" };
+            footer.push(Line::from(vec![
+                "/".bold(),
+                search.query.as_str().into(),
+                cursor.into(),
+                format!("  ({matches} matches)").fg(Color::Gray),
+            ]));
+        }
+        let prompt = self.overlays.iter().find_map(|overlay| match overlay {
+            ListOverlay::Prompt(prompt) => Some(prompt),
+            _ => None,
+        });
+        if let Some(prompt) = prompt {
+            let mut line = prompt.input.line(true);
+            line.spans.insert(0, prompt.kind.prefix().bold());
+            footer.push(line);
+            if !prompt.candidates.is_empty() {
+                footer.push(Line::from(prompt.candidates.join("  ")).fg(Color::Gray));
+            }
+            if let Some(err) = prompt.error.as_deref() {
+                footer.push(Line::from(err).red());
+            }
+        }
+        let [content, footer_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(footer.len() as u16)])
+                .areas(content);
+        Paragraph::new(footer).render(footer_area, buf);
+        let grouped = self.group_by != GroupBy::None;
+        match self.layout {
+            ListLayout::Cards if grouped => {
+                let entries = group::entries(&self.rows, &self.groups, &self.items);
+                List::new(&entries).render(content, buf, &mut self.state);
+            }
+            ListLayout::Cards => List::new(&self.items).render(content, buf, &mut self.state),
+            ListLayout::Table => {
+                let [header, content] =
+                    Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(content);
+                // align the header with the rows, which are shifted by the selection marker and
+                // the scrollbar
+                let overflowing = self.rows.len() > content.height as usize;
+                let indent = if grouped { 4 } else { 2 };
+                let header = Rect {
+                    x: header.x + indent,
+                    width: header.width.saturating_sub(indent + u16::from(overflowing)),
+                    ..header
+                };
+                TableHeader(&self.columns).render(header, buf);
+                let rows = self
+                    .items
+                    .iter()
+                    .map(|item| TableRow {
+                        item,
+                        columns: &self.columns,
+                    })
+                    .collect::<Vec<_>>();
+                if grouped {
+                    let entries = group::entries(&self.rows, &self.groups, &rows);
+                    List::new(&entries).render(content, buf, &mut self.state);
+                } else {
+                    List::new(&rows).render(content, buf, &mut self.state);
+                }
+            }
+        }
+
+        for overlay in &self.overlays {
+            match overlay {
+                ListOverlay::Menu(menu) => menu.widget().render(area, buf),
+                ListOverlay::Dialog(_, dialog) => dialog.render(area, buf),
+                ListOverlay::Search | ListOverlay::Prompt(_) => {}
+            }
+        }
+    }
+}
+
+/// Adds the value at the end of the history, removing its previous occurrence.
+fn remember(history: &mut Vec<String>, value: &str) {
+    history.retain(|previous| previous != value);
+    history.push(value.to_string());
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use transmission_rpc::types::Torrent;
+
+    use super::ListView;
+    use crate::view::list::filter::StatusFilter;
+    use crate::view::list::sort::Sort;
+
+    /// Torrents `a` to `e`, queued in the reverse order, `b` and `d` being stopped.
+    fn torrents() -> Vec<Torrent> {
+        ["a", "b", "c", "d", "e"]
+            .into_iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let status = if index % 2 == 1 { 0 } else { 4 };
+                serde_json::from_value(json!({
+                    "id": index + 1,
+                    "name": name,
+                    "status": status,
+                    "queuePosition": 5 - index,
+                }))
+                .unwrap()
+            })
+            .collect()
+    }
+
+    fn view() -> ListView {
+        let mut view = ListView {
+            torrents: torrents(),
+            sort: Sort::parse("name").unwrap(),
+            ..ListView::default()
+        };
+        view.rebuild_items();
+        view
+    }
+
+    fn select(view: &mut ListView, id: i64) {
+        let row = view
+            .items
+            .iter()
+            .position(|item| item.torrent.id == Some(id));
+        view.state.select(row);
+    }
+
+    #[test]
+    fn marks_are_flagged_when_rebuilt() {
+        let mut view = view();
+        view.marked.extend([1, 2]);
+        view.filter = StatusFilter::Downloading;
+        view.rebuild_items();
+        let marked = view
+            .items
+            .iter()
+            .filter(|item| item.marked)
+            .filter_map(|item| item.torrent.id)
+            .collect::<Vec<_>>();
+        // the stopped torrent is hidden, yet still marked
+        assert_eq!(marked, vec![1]);
+        assert_eq!(view.marked_ids().len(), 2);
+    }
+
+    #[test]
+    fn marks_are_cleared_once_the_action_succeeded() {
+        let (context, _actions, _events) = crate::Context::for_tests();
+        let mut view = view();
+        view.marked.extend([1, 2, 3]);
+        view.update(crate::Event::TorrentChange(vec![1, 2]), &context);
+        assert_eq!(view.marked_ids().into_iter().collect::<Vec<_>>(), vec![3]);
+        assert!(view.items.iter().any(|item| item.marked));
+    }
+
+    #[test]
+    fn selection_follows_the_torrent_when_sorted() {
+        let mut view = view();
+        select(&mut view, 2);
+        view.sort = Sort::parse("queue").unwrap();
+        view.rebuild_items();
+        assert_eq!(view.selected_id(), Some(2));
+        assert_eq!(view.state.selected(), Some(3));
+    }
+
+    #[test]
+    fn selection_follows_the_torrent_across_refreshes() {
+        let mut view = view();
+        select(&mut view, 3);
+        view.torrents.retain(|torrent| torrent.id != Some(1));
+        view.rebuild_items();
+        assert_eq!(view.selected_id(), Some(3));
+        assert_eq!(view.state.selected(), Some(1));
+    }
+
+    #[test]
+    fn selection_falls_back_to_the_nearest_neighbor_when_filtered() {
+        let mut view = view();
+        select(&mut view, 2);
+        view.filter = StatusFilter::Downloading;
+        view.rebuild_items();
+        // the next rows are looked at first
+        assert_eq!(view.selected_id(), Some(3));
+        select(&mut view, 5);
+        view.torrents.retain(|torrent| torrent.id != Some(5));
+        view.rebuild_items();
+        assert_eq!(view.selected_id(), Some(3));
+    }
+}
@@ -0,0 +1,337 @@
+use crate::components::completion::{Argument, Completion, complete_with};
+use crate::components::parse_speed;
+use crate::keymap::Command;
+use crate::view::list::filter::StatusFilter;
+use crate::view::list::sort::SortField;
+
+/// Commands of the list that make sense from the palette, in addition to its own ones.
+const LIST_COMMANDS: [Command; 34] = [
+    Command::Quit,
+    Command::Help,
+    Command::Log,
+    Command::Inspector,
+    Command::Reload,
+    Command::Open,
+    Command::Menu,
+    Command::AddTorrent,
+    Command::SpeedLimits,
+    Command::First,
+    Command::Last,
+    Command::FocusDetail,
+    Command::ToggleSplit,
+    Command::ToggleLayout,
+    Command::NextGroup,
+    Command::NextSort,
+    Command::ToggleSortDirection,
+    Command::NextFilter,
+    Command::PreviousFilter,
+    Command::Search,
+    Command::NextMatch,
+    Command::PreviousMatch,
+    Command::Query,
+    Command::MarkByQuery,
+    Command::ToggleMark,
+    Command::VisualMark,
+    Command::MarkAll,
+    Command::ClearMarks,
+    Command::Start,
+    Command::Pause,
+    Command::Verify,
+    Command::Move,
+    Command::Labels,
+    Command::Delete,
+];
+
+const NAMES: [&str; 13] = [
+    "add", "start", "stop", "verify", "delete", "filter", "mark", "sort", "limit", "profile",
+    "move", "move!", "label",
+];
+
+const QUERY_KEYS: [&str; 12] = [
+    "name:",
+    "status:",
+    "label:",
+    "tracker:",
+    "dir:",
+    "ratio>",
+    "size>",
+    "progress<",
+    "down>",
+    "up>",
+    "peers>",
+    "added<",
+];
+
+/// Torrents an action of the palette applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Target {
+    /// The marked torrents, or else the selected one.
+    Selection,
+    /// Every visible torrent.
+    All,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Direction {
+    Download,
+    Upload,
+}
+
+/// Command typed in the palette.
+#[derive(Debug, PartialEq)]
+pub(super) enum PaletteCommand {
+    Add(String),
+    Start(Target),
+    Stop(Target),
+    Verify(Target),
+    Delete(Target),
+    /// Filters the list with a query, clearing the filter when empty.
+    Filter(String),
+    Mark(String),
+    Sort(SortField, Option<bool>),
+    /// Speed limit in kB/s, removed when `None`.
+    Limit(Direction, Option<i32>),
+    Profile(String),
+    /// Sets the location of the torrents, moving their data there when `true`.
+    Move(String, bool),
+    /// Replaces the labels, removing them all when empty.
+    Label(Vec<String>),
+    Run(Command),
+}
+
+fn parse_target(argument: &str) -> Result<Target, String> {
+    match argument {
+        "" | "marked" | "selected" => Ok(Target::Selection),
+        "all" => Ok(Target::All),
+        other => Err(format!("unknown target {other:?}, expected \"all\"")),
+    }
+}
+
+fn parse_limit(argument: &str) -> Result<PaletteCommand, String> {
+    let mut words = argument.split_whitespace();
+    let direction = match words.next() {
+        Some("down") => Direction::Download,
+        Some("up") => Direction::Upload,
+        _ => return Err("Usage: limit <down|up> <speed|none>".to_string()),
+    };
+    let limit = match words.next() {
+        None | Some("none") | Some("off") => None,
+        Some(value) => Some(parse_speed(value).ok_or_else(|| format!("invalid speed {value:?}"))?),
+    };
+    Ok(PaletteCommand::Limit(direction, limit))
+}
+
+fn parse_sort(argument: &str) -> Result<PaletteCommand, String> {
+    let mut words = argument.split_whitespace();
+    let field = words
+        .next()
+        .ok_or_else(|| "Usage: sort <field> [asc|desc]".to_string())?;
+    let field = SortField::from_name(field).ok_or_else(|| format!("unknown field {field:?}"))?;
+    let descending = match words.next() {
+        None => None,
+        Some("asc") => Some(false),
+        Some("desc") => Some(true),
+        Some(other) => return Err(format!("unknown direction {other:?}")),
+    };
+    Ok(PaletteCommand::Sort(field, descending))
+}
+
+fn required(argument: &str, usage: &str) -> Result<String, String> {
+    if argument.is_empty() {
+        Err(format!("Usage: {usage}"))
+    } else {
+        Ok(argument.to_string())
+    }
+}
+
+/// Parses the text typed after the `:`.
+pub(super) fn parse(text: &str) -> Result<PaletteCommand, String> {
+    let text = text.trim();
+    let (name, argument) = text
+        .split_once(char::is_whitespace)
+        .map(|(name, argument)| (name, argument.trim()))
+        .unwrap_or((text, ""));
+    match name {
+        "add" if argument.is_empty() => Ok(PaletteCommand::Run(Command::AddTorrent)),
+        "add" => Ok(PaletteCommand::Add(argument.to_string())),
+        "start" => parse_target(argument).map(PaletteCommand::Start),
+        "stop" | "pause" => parse_target(argument).map(PaletteCommand::Stop),
+        "verify" => parse_target(argument).map(PaletteCommand::Verify),
+        "delete" => parse_target(argument).map(PaletteCommand::Delete),
+        "filter" => Ok(PaletteCommand::Filter(argument.to_string())),
+        "mark" => required(argument, "mark <query>").map(PaletteCommand::Mark),
+        "sort" => parse_sort(argument),
+        "limit" if argument.is_empty() => Ok(PaletteCommand::Run(Command::SpeedLimits)),
+        "limit" => parse_limit(argument),
+        "profile" => required(argument, "profile <name>").map(PaletteCommand::Profile),
+        // the data is only moved when asked to, the location may point at a copy of it already
+        "move" | "move!" => required(argument, "move[!] <directory>")
+            .map(|directory| PaletteCommand::Move(directory, name == "move!")),
+        "label" if argument == "none" => Ok(PaletteCommand::Label(Vec::new())),
+        "label" => Some(split_labels(argument))
+            .filter(|labels| !labels.is_empty())
+            .map(PaletteCommand::Label)
+            .ok_or_else(|| "Usage: label <a,b,…|none>".to_string()),
+        "" => Err("Type a command, like \"start all\"".to_string()),
+        other => Command::from_name(other)
+            .filter(|command| LIST_COMMANDS.contains(command) && argument.is_empty())
+            .map(PaletteCommand::Run)
+            .ok_or_else(|| format!("unknown command {other:?}")),
+    }
+}
+
+pub(super) fn split_labels(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|label| !label.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Values known from the torrents and the configuration, offered by the completion.
+#[derive(Debug, Default)]
+pub(super) struct Sources {
+    pub(super) labels: Vec<String>,
+    pub(super) directories: Vec<String>,
+    pub(super) trackers: Vec<String>,
+    pub(super) profiles: Vec<String>,
+}
+
+/// Candidates of the terms of a query, with the values of the text conditions.
+pub(super) fn query_candidates(sources: &Sources) -> Vec<String> {
+    let values = |key: &str, values: &[String]| {
+        values
+            .iter()
+            .filter(|value| !value.is_empty())
+            .map(|value| {
+                if value.contains(char::is_whitespace) {
+                    format!("{key}:\"{value}\"")
+                } else {
+                    format!("{key}:{value}")
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    QUERY_KEYS
+        .iter()
+        .map(|key| key.to_string())
+        .chain(
+            StatusFilter::ALL
+                .iter()
+                .map(|status| format!("status:{}", status.label().to_lowercase())),
+        )
+        .chain(values("label", &sources.labels))
+        .chain(values("tracker", &sources.trackers))
+        .chain(values("dir", &sources.directories))
+        .collect()
+}
+
+/// Completes the command, or the argument of the command, being typed in the palette.
+pub(super) fn complete(text: &str, sources: &Sources) -> Completion {
+    let Some((name, argument)) = text.split_once(char::is_whitespace) else {
+        let names = NAMES
+            .iter()
+            .map(|name| name.to_string())
+            .chain(
+                LIST_COMMANDS
+                    .iter()
+                    .map(|command| command.name().to_string()),
+            )
+            .collect::<Vec<_>>();
+        return complete_with(text, Argument::Words, &names);
+    };
+    let head = &text[..text.len() - argument.len()];
+    let words = |values: &[&str]| values.iter().map(|value| value.to_string()).collect();
+    let position = argument.split_whitespace().count()
+        - usize::from(!argument.is_empty() && !argument.ends_with(char::is_whitespace));
+    let (kind, candidates): (Argument, Vec<String>) = match (name, position) {
+        ("start" | "stop" | "pause" | "verify" | "delete", 0) => {
+            (Argument::Words, words(&["all", "marked"]))
+        }
+        ("filter" | "mark", _) => (Argument::Words, query_candidates(sources)),
+        ("sort", 0) => (
+            Argument::Words,
+            SortField::ALL
+                .iter()
+                .map(|field| field.name().to_string())
+                .collect(),
+        ),
+        ("sort", 1) => (Argument::Words, words(&["asc", "desc"])),
+        ("limit", 0) => (Argument::Words, words(&["down", "up"])),
+        ("limit", 1) => (Argument::Words, words(&["none"])),
+        ("profile", 0) => (Argument::Words, sources.profiles.clone()),
+        ("move" | "move!", _) => (Argument::Rest, sources.directories.clone()),
+        ("label", _) => (Argument::List, sources.labels.clone()),
+        _ => {
+            return Completion {
+                text: text.to_string(),
+                candidates: Vec::new(),
+            };
+        }
+    };
+    let completion = complete_with(argument, kind, &candidates);
+    Completion {
+        text: format!("{head}{}", completion.text),
+        candidates: completion.candidates,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PaletteCommand, Sources, Target, complete, parse};
+
+    #[test]
+    fn parse_reads_the_targets() {
+        assert_eq!(parse("start"), Ok(PaletteCommand::Start(Target::Selection)));
+        assert_eq!(parse(" stop  all "), Ok(PaletteCommand::Stop(Target::All)));
+        assert!(parse("verify some").is_err());
+    }
+
+    #[test]
+    fn parse_requires_the_labels() {
+        assert_eq!(
+            parse("label a, b ,,c"),
+            Ok(PaletteCommand::Label(vec![
+                "a".to_string(),
+                "b".to_string(),
+                "c".to_string()
+            ]))
+        );
+        assert_eq!(parse("label none"), Ok(PaletteCommand::Label(Vec::new())));
+        assert!(parse("label").is_err());
+        assert!(parse("label ,").is_err());
+    }
+
+    #[test]
+    fn parse_moves_the_data_when_asked_to() {
+        assert_eq!(
+            parse("move /data/linux isos"),
+            Ok(PaletteCommand::Move("/data/linux isos".to_string(), false))
+        );
+        assert_eq!(
+            parse("move! /data"),
+            Ok(PaletteCommand::Move("/data".to_string(), true))
+        );
+        assert!(parse("move!").is_err());
+    }
+
+    #[test]
+    fn complete_reads_the_arguments_of_move() {
+        let sources = Sources {
+            directories: vec!["/data".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(complete("move! /d", &sources).text, "move! /data");
+    }
+
+    #[test]
+    fn complete_keeps_the_separator() {
+        let sources = Sources {
+            labels: vec!["linux".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(complete("start a", &sources).text, "start all ");
+        assert_eq!(complete("start\u{a0}a", &sources).text, "start\u{a0}all ");
+        assert_eq!(complete("label  li", &sources).text, "label linux");
+    }
+}
@@ -0,0 +1,57 @@
+use crate::components::input::TextInput;
+
+/// What the text typed in the prompt is used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) enum PromptKind {
+    /// Filters the list with a query.
+    Query,
+    /// Marks every torrent matching a query.
+    Mark,
+    /// Replaces the labels of the targeted torrents, separated by commas.
+    Labels,
+    /// Runs a command of the palette.
+    Command,
+}
+
+impl PromptKind {
+    pub(super) const fn label(&self) -> &'static str {
+        match self {
+            Self::Query => "Query",
+            Self::Mark => "Mark",
+            Self::Labels => "Labels",
+            Self::Command => "Command",
+        }
+    }
+}
+
+impl PromptKind {
+    /// Text displayed before the typed one.
+    pub(super) fn prefix(&self) -> String {
+        match self {
+            Self::Command => ":".to_string(),
+            other => format!("{}: ", other.label()),
+        }
+    }
+}
+
+/// Text being typed in the prompt at the bottom of the list.
+#[derive(Debug)]
+pub(super) struct Prompt {
+    pub(super) kind: PromptKind,
+    pub(super) input: TextInput,
+    pub(super) error: Option<String>,
+    /// Completions left after the last tab, when several match.
+    pub(super) candidates: Vec<String>,
+}
+
+impl Prompt {
+    /// Prompt starting with the text, the history holding the previous texts of its kind.
+    pub(super) fn new(kind: PromptKind, text: String, history: Vec<String>) -> Self {
+        Self {
+            kind,
+            input: TextInput::new(text).with_history(history),
+            error: None,
+            candidates: Vec::new(),
+        }
+    }
+}
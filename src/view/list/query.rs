@@ -0,0 +1,424 @@
+//! Filter expressions over the torrents, like `status:seeding ratio>2 size>10G added<7d`.
+//!
+//! The terms are separated by spaces and all of them have to match. A term can be negated with
+//! a leading `-` and a word without any operator is matched against the name.
+
+use std::fmt;
+
+use chrono::Utc;
+use transmission_rpc::types::Torrent;
+
+use crate::components::{parse_duration, parse_size, tracker_hosts};
+use crate::view::list::filter::StatusFilter;
+
+#[derive(Debug)]
+pub(super) struct ParseError(String);
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operator {
+    /// `:`, containing for the name, tracker and directory, and equal for the label, the status
+    /// and the numeric fields.
+    Colon,
+    Equal,
+    Greater,
+    GreaterOrEqual,
+    Lower,
+    LowerOrEqual,
+}
+
+impl Operator {
+    /// Ordered so that the two characters operators are found before the single ones.
+    const ALL: [(&'static str, Operator); 6] = [
+        (">=", Operator::GreaterOrEqual),
+        ("<=", Operator::LowerOrEqual),
+        (":", Operator::Colon),
+        ("=", Operator::Equal),
+        (">", Operator::Greater),
+        ("<", Operator::Lower),
+    ];
+
+    fn compare(self, left: f64, right: f64) -> bool {
+        match self {
+            Self::Colon | Self::Equal => (left - right).abs() < f64::EPSILON,
+            Self::Greater => left > right,
+            Self::GreaterOrEqual => left >= right,
+            Self::Lower => left < right,
+            Self::LowerOrEqual => left <= right,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum NumericField {
+    Ratio,
+    Size,
+    Progress,
+    DownloadSpeed,
+    UploadSpeed,
+    Peers,
+    /// Time since the torrent has been added, in seconds.
+    Added,
+}
+
+impl NumericField {
+    fn parse_value(self, value: &str) -> Option<f64> {
+        match self {
+            Self::Ratio | Self::Peers => value.parse().ok(),
+            Self::Size | Self::DownloadSpeed | Self::UploadSpeed => parse_size(value),
+            Self::Progress => value.trim_end_matches('%').parse().ok(),
+            Self::Added => parse_duration(value).map(|value| value as f64),
+        }
+    }
+
+    fn value(self, item: &Torrent) -> Option<f64> {
+        match self {
+            // Transmission reports `-1` when there is no ratio and `-2` when it is infinite
+            Self::Ratio => item
+                .upload_ratio
+                .map(f64::from)
+                .filter(|ratio| *ratio >= 0.0),
+            Self::Size => item.total_size.map(|value| value as f64),
+            Self::Progress => item.percent_done.map(|value| value as f64 * 100.0),
+            Self::DownloadSpeed => item.rate_download.map(|value| value as f64),
+            Self::UploadSpeed => item.rate_upload.map(|value| value as f64),
+            Self::Peers => item.peers_connected.map(|value| value as f64),
+            Self::Added => item
+                .added_date
+                .filter(|date| *date > chrono::DateTime::UNIX_EPOCH)
+                .map(|date| (Utc::now() - date).num_seconds() as f64),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Condition {
+    Name(String),
+    Status(StatusFilter),
+    Label(String),
+    Tracker(String),
+    Directory(String),
+    Numeric(NumericField, Operator, f64),
+}
+
+fn contains(haystack: &str, needle: &str) -> bool {
+    haystack.to_lowercase().contains(needle)
+}
+
+impl Condition {
+    fn parse(term: &str) -> Result<Self, ParseError> {
+        // a quoted term is a name, even when it contains an operator
+        if term.starts_with('"') {
+            return Ok(Self::Name(unquote(term).to_lowercase()));
+        }
+        let Some((index, operator_str, operator)) = term.char_indices().find_map(|(index, _)| {
+            Operator::ALL
+                .iter()
+                .find(|(symbol, _)| term[index..].starts_with(symbol))
+                .map(|(symbol, operator)| (index, *symbol, *operator))
+        }) else {
+            return Ok(Self::Name(term.to_lowercase()));
+        };
+        let key = term[..index].to_lowercase();
+        let value = unquote(&term[index + operator_str.len()..]);
+        if value.is_empty() {
+            return Err(ParseError(format!("missing value after {key:?}")));
+        }
+        let text = |build: fn(String) -> Condition| {
+            if operator == Operator::Colon || operator == Operator::Equal {
+                Ok(build(value.to_lowercase()))
+            } else {
+                Err(ParseError(format!("{key:?} only supports \":\"")))
+            }
+        };
+        let numeric = match key.as_str() {
+            "name" => return text(Self::Name),
+            "label" => return text(Self::Label),
+            "tracker" => return text(Self::Tracker),
+            "dir" => return text(Self::Directory),
+            "status" => {
+                let status = StatusFilter::ALL
+                    .iter()
+                    .find(|filter| filter.label().eq_ignore_ascii_case(value))
+                    .copied()
+                    .or_else(|| {
+                        value
+                            .eq_ignore_ascii_case("stopped")
+                            .then_some(StatusFilter::Paused)
+                    })
+                    .ok_or_else(|| ParseError(format!("unknown status {value:?}")))?;
+                return Ok(Self::Status(status));
+            }
+            "ratio" => NumericField::Ratio,
+            "size" => NumericField::Size,
+            "progress" => NumericField::Progress,
+            "down" => NumericField::DownloadSpeed,
+            "up" => NumericField::UploadSpeed,
+            "peers" => NumericField::Peers,
+            "added" => NumericField::Added,
+            other => return Err(ParseError(format!("unknown field {other:?}"))),
+        };
+        let parsed = numeric
+            .parse_value(value)
+            .ok_or_else(|| ParseError(format!("invalid value {value:?} for {key:?}")))?;
+        Ok(Self::Numeric(numeric, operator, parsed))
+    }
+
+    fn matches(&self, item: &Torrent) -> bool {
+        match self {
+            Self::Name(value) => item
+                .name
+                .as_deref()
+                .is_some_and(|name| contains(name, value)),
+            Self::Status(filter) => filter.matches(item),
+            Self::Label(value) => item
+                .labels
+                .iter()
+                .flatten()
+                .any(|label| label.eq_ignore_ascii_case(value)),
+            Self::Tracker(value) => tracker_hosts(item).any(|host| contains(&host, value)),
+            Self::Directory(value) => item
+                .download_dir
+                .as_deref()
+                .is_some_and(|dir| contains(dir, value)),
+            Self::Numeric(field, operator, expected) => field
+                .value(item)
+                .is_some_and(|value| operator.compare(value, *expected)),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Term {
+    negated: bool,
+    condition: Condition,
+}
+
+#[derive(Debug)]
+pub(super) struct Query {
+    source: String,
+    terms: Vec<Term>,
+}
+
+impl Query {
+    /// Parses the query, returning `None` when it's empty.
+    pub(super) fn parse(source: &str) -> Result<Option<Self>, ParseError> {
+        let terms = tokenize(source)?
+            .into_iter()
+            .map(|token| {
+                let (negated, token) = match token.strip_prefix('-') {
+                    Some(rest) if !rest.is_empty() => (true, rest),
+                    _ => (false, token.as_str()),
+                };
+                Condition::parse(token).map(|condition| Term { negated, condition })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((!terms.is_empty()).then(|| Self {
+            source: source.trim().to_string(),
+            terms,
+        }))
+    }
+
+    pub(super) fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub(super) fn matches(&self, item: &Torrent) -> bool {
+        self.terms
+            .iter()
+            .all(|term| term.condition.matches(item) != term.negated)
+    }
+}
+
+/// Splits the query on the spaces, except the ones between double quotes.
+fn tokenize(source: &str) -> Result<Vec<String>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in source.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                current.push(c);
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if quoted {
+        return Err(ParseError("unterminated quote".into()));
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use serde_json::json;
+    use transmission_rpc::types::Torrent;
+
+    use super::{Condition, Query, tokenize};
+
+    fn torrent(value: serde_json::Value) -> Torrent {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn ubuntu() -> Torrent {
+        torrent(json!({
+            "id": 1,
+            "name": "Ubuntu Server 24.04",
+            "status": 6,
+            "labels": ["linux", "iso"],
+            "downloadDir": "/data/Images",
+            "uploadRatio": 2.5,
+            "totalSize": 3_000_000_000i64,
+            "percentDone": 1.0,
+            "rateDownload": 0,
+            "rateUpload": 150_000,
+            "peersConnected": 4,
+            "addedDate": Utc::now().timestamp() - 2 * 86_400,
+            "trackers": [{
+                "id": 0,
+                "announce": "https://tracker.example.org/announce",
+                "scrape": "",
+                "tier": 0,
+            }],
+        }))
+    }
+
+    fn matches(source: &str, item: &Torrent) -> bool {
+        Query::parse(source).unwrap().unwrap().matches(item)
+    }
+
+    #[test]
+    fn tokenize_keeps_the_quoted_spaces() {
+        assert_eq!(
+            tokenize(r#"  dir:"/data/my files"  ratio>1 "#).unwrap(),
+            vec![r#"dir:"/data/my files""#, "ratio>1"]
+        );
+        assert!(tokenize(r#"name:"open"#).is_err());
+    }
+
+    #[test]
+    fn parse_returns_none_for_an_empty_query() {
+        assert!(Query::parse("   ").unwrap().is_none());
+    }
+
+    #[test]
+    fn parse_finds_the_two_characters_operators_first() {
+        let item = ubuntu();
+        assert!(matches("ratio>=2.5", &item));
+        assert!(!matches("ratio>2.5", &item));
+        assert!(matches("ratio<=2.5", &item));
+        assert!(matches("ratio=2.5", &item));
+    }
+
+    #[test]
+    fn parse_rejects_the_invalid_terms() {
+        for source in [
+            "foo:bar",
+            "ratio:",
+            "status:sleeping",
+            "label>linux",
+            "size>huge",
+            "added<7é",
+            "added<99999999999999999d",
+        ] {
+            assert!(Query::parse(source).is_err(), "{source}");
+        }
+    }
+
+    #[test]
+    fn bare_words_match_the_name() {
+        let item = ubuntu();
+        assert!(matches("ubuntu", &item));
+        assert!(matches("SERVER", &item));
+        assert!(!matches("debian", &item));
+    }
+
+    #[test]
+    fn quoted_bare_terms_match_the_name() {
+        let item = ubuntu();
+        assert!(matches(r#""ubuntu server""#, &item));
+        assert!(matches(r#""24.04""#, &item));
+        assert!(!matches(r#""server ubuntu""#, &item));
+        assert!(matches(r#"-"ubuntu desktop""#, &item));
+        assert!(matches!(
+            Condition::parse(r#""a:b""#),
+            Ok(Condition::Name(name)) if name == "a:b"
+        ));
+    }
+
+    #[test]
+    fn text_fields_match() {
+        let item = ubuntu();
+        assert!(matches("label:LINUX", &item));
+        assert!(!matches("label:lin", &item));
+        assert!(matches("tracker:example", &item));
+        assert!(matches(r#"dir:"/data/images""#, &item));
+        assert!(matches("status:seeding", &item));
+        assert!(!matches("status:stopped", &item));
+    }
+
+    #[test]
+    fn numeric_fields_match() {
+        let item = ubuntu();
+        assert!(matches("size>1G", &item));
+        assert!(!matches("size>3GB", &item));
+        assert!(matches("size<3GiB", &item));
+        assert!(matches("progress=100%", &item));
+        assert!(matches("up>100k down<1", &item));
+        assert!(matches("peers>=4", &item));
+        assert!(matches("added<3d", &item));
+        assert!(!matches("added<1d", &item));
+    }
+
+    #[test]
+    fn terms_are_combined_and_negated() {
+        let item = ubuntu();
+        assert!(matches("ubuntu -label:windows", &item));
+        assert!(!matches("ubuntu -label:iso", &item));
+        assert!(!matches("ubuntu label:windows", &item));
+    }
+
+    #[test]
+    fn unknown_ratios_never_match() {
+        for ratio in [-1.0, -2.0] {
+            let item = torrent(json!({ "id": 3, "uploadRatio": ratio }));
+            assert!(!matches("ratio<1", &item));
+            assert!(!matches("ratio>=0", &item));
+        }
+        let item = torrent(json!({ "id": 3, "uploadRatio": 0.0 }));
+        assert!(matches("ratio<1", &item));
+    }
+
+    #[test]
+    fn missing_values_never_match() {
+        let item = torrent(json!({ "id": 2 }));
+        assert!(!matches("ubuntu", &item));
+        assert!(!matches("ratio<1", &item));
+        assert!(!matches("added<7d", &item));
+        assert!(matches("-label:linux", &item));
+    }
+}
@@ -0,0 +1,171 @@
+use ratatui::style::Stylize;
+use ratatui::text::Span;
+
+/// Incremental search over the names of the visible torrents.
+#[derive(Debug, Default)]
+pub(super) struct Search {
+    pub(super) query: String,
+}
+
+const CONSECUTIVE_BONUS: i64 = 5;
+const WORD_START_BONUS: i64 = 3;
+
+/// Matches the pattern characters in order in the text, ignoring the case.
+///
+/// Returns the score of the best match, higher being better, and the positions of its matched
+/// characters. Every way of matching the pattern is considered, so that a later occurrence
+/// starting a word or continuing a run wins over the first one.
+pub(super) fn fuzzy_match(pattern: &str, text: &str) -> Option<(i64, Vec<usize>)> {
+    let pattern = pattern
+        .chars()
+        .flat_map(char::to_lowercase)
+        .collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+    if pattern.is_empty() {
+        return Some((0, Vec::new()));
+    }
+    if pattern.len() > text.len() {
+        return None;
+    }
+    let matches = |p: usize, t: usize| text[t].to_lowercase().eq(std::iter::once(pattern[p]));
+    let bonus = |t: usize| {
+        let word_start = t == 0 || !text[t - 1].is_alphanumeric();
+        1 + if word_start { WORD_START_BONUS } else { 0 }
+    };
+    // scores[p][t]: best score of the pattern up to `p` with its character `p` matched at `t`,
+    // along with the position matching the previous character of the pattern
+    let mut scores: Vec<Vec<Option<(i64, usize)>>> = vec![vec![None; text.len()]; pattern.len()];
+    for (t, score) in scores[0].iter_mut().enumerate() {
+        if matches(0, t) {
+            *score = Some((bonus(t), t));
+        }
+    }
+    for p in 1..pattern.len() {
+        // best score of the previous character matched before `t - 1`
+        let mut best_before: Option<(i64, usize)> = None;
+        for t in p..text.len() {
+            let adjacent =
+                scores[p - 1][t - 1].map(|(score, _)| (score + CONSECUTIVE_BONUS, t - 1));
+            if matches(p, t) {
+                scores[p][t] = [best_before, adjacent]
+                    .into_iter()
+                    .flatten()
+                    .max_by_key(|(score, _)| *score)
+                    .map(|(score, previous)| (score + bonus(t), previous));
+            }
+            if let Some((score, _)) = scores[p - 1][t - 1]
+                && best_before.is_none_or(|(best, _)| score > best)
+            {
+                best_before = Some((score, t - 1));
+            }
+        }
+    }
+    let (score, end) = (0..text.len())
+        .filter_map(|t| scores[pattern.len() - 1][t].map(|(score, _)| (score, t)))
+        .max_by_key(|(score, t)| (*score, std::cmp::Reverse(*t)))?;
+    let mut positions = vec![end];
+    for p in (1..pattern.len()).rev() {
+        let (_, previous) = scores[p][positions[0]]?;
+        positions.insert(0, previous);
+    }
+    // prefer the matches starting early in the name
+    let first = positions[0] as i64;
+    Some((score * 100 - first, positions))
+}
+
+/// Splits the text in spans, underlining the characters at the given positions.
+pub(super) fn highlight<'a>(text: &'a str, positions: &[usize]) -> Vec<Span<'a>> {
+    if positions.is_empty() {
+        return vec![Span::raw(text)];
+    }
+    let mut spans = Vec::new();
+    let mut start = 0;
+    let mut current = false;
+    for (index, (offset, _)) in text.char_indices().enumerate() {
+        let matched = positions.contains(&index);
+        if matched != current && offset > start {
+            spans.push(styled(&text[start..offset], current));
+            start = offset;
+        }
+        current = matched;
+    }
+    spans.push(styled(&text[start..], current));
+    spans
+}
+
+fn styled(text: &str, matched: bool) -> Span<'_> {
+    if matched {
+        text.yellow().underlined()
+    } else {
+        Span::raw(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fuzzy_match, highlight};
+
+    #[test]
+    fn fuzzy_match_finds_the_characters_in_order() {
+        let (_, positions) = fuzzy_match("ubs", "ubuntu server").unwrap();
+        assert_eq!(positions, vec![0, 1, 7]);
+        assert!(fuzzy_match("sbu", "ubuntu server").is_none());
+        assert!(fuzzy_match("ubuntux", "ubuntu").is_none());
+    }
+
+    #[test]
+    fn fuzzy_match_ignores_the_case() {
+        assert!(fuzzy_match("DEB", "debian").is_some());
+        assert!(fuzzy_match("deb", "DEBIAN").is_some());
+    }
+
+    #[test]
+    fn fuzzy_match_counts_the_positions_in_characters() {
+        let (_, positions) = fuzzy_match("ét", "café été").unwrap();
+        assert_eq!(positions, vec![5, 6]);
+    }
+
+    #[test]
+    fn fuzzy_match_prefers_consecutive_and_early_matches() {
+        let (consecutive, _) = fuzzy_match("arch", "archlinux").unwrap();
+        let (scattered, _) = fuzzy_match("arch", "a rather cheap").unwrap();
+        assert!(consecutive > scattered);
+        let (early, _) = fuzzy_match("iso", "iso image").unwrap();
+        let (late, _) = fuzzy_match("iso", "image iso").unwrap();
+        assert!(early > late);
+    }
+
+    #[test]
+    fn fuzzy_match_finds_the_best_occurrence() {
+        // the first `a` starts a word, but the later one is followed by the `b`
+        let (_, positions) = fuzzy_match("ab", "a-xab").unwrap();
+        assert_eq!(positions, vec![3, 4]);
+        let (_, positions) = fuzzy_match("lin", "slackware linux").unwrap();
+        assert_eq!(positions, vec![10, 11, 12]);
+    }
+
+    #[test]
+    fn fuzzy_match_ranks_the_same_name_the_same_way() {
+        let (first, _) = fuzzy_match("deb", "debian debian").unwrap();
+        let (second, _) = fuzzy_match("deb", "debian").unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn fuzzy_match_prefers_word_starts() {
+        let (word_start, _) = fuzzy_match("s", "big server").unwrap();
+        let (inside, _) = fuzzy_match("s", "big users").unwrap();
+        assert!(word_start > inside);
+    }
+
+    #[test]
+    fn highlight_splits_on_character_positions() {
+        let spans = highlight("café", &[2, 3]);
+        let texts = spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect::<Vec<_>>();
+        assert_eq!(texts, vec!["ca", "fé"]);
+        assert_eq!(highlight("plain", &[]).len(), 1);
+    }
+}
@@ -0,0 +1,275 @@
+use std::cmp::Ordering;
+
+use transmission_rpc::types::Torrent;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(super) enum SortField {
+    Name,
+    Status,
+    Progress,
+    DownloadSpeed,
+    UploadSpeed,
+    Ratio,
+    Size,
+    Eta,
+    /// Same default as the transmission web interface.
+    #[default]
+    QueuePosition,
+    AddedDate,
+    SeedingTime,
+}
+
+impl SortField {
+    pub(super) const ALL: [SortField; 11] = [
+        SortField::Name,
+        SortField::Status,
+        SortField::Progress,
+        SortField::DownloadSpeed,
+        SortField::UploadSpeed,
+        SortField::Ratio,
+        SortField::Size,
+        SortField::Eta,
+        SortField::QueuePosition,
+        SortField::AddedDate,
+        SortField::SeedingTime,
+    ];
+
+    pub(super) const fn label(self) -> &'static str {
+        match self {
+            Self::Name => "Name",
+            Self::Status => "Status",
+            Self::Progress => "Progress",
+            Self::DownloadSpeed => "Download speed",
+            Self::UploadSpeed => "Upload speed",
+            Self::Ratio => "Ratio",
+            Self::Size => "Size",
+            Self::Eta => "ETA",
+            Self::QueuePosition => "Queue",
+            Self::AddedDate => "Date added",
+            Self::SeedingTime => "Seeding time",
+        }
+    }
+
+    /// Name of the field in the configuration and the command palette.
+    pub(super) const fn name(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Status => "status",
+            Self::Progress => "progress",
+            Self::DownloadSpeed => "down",
+            Self::UploadSpeed => "up",
+            Self::Ratio => "ratio",
+            Self::Size => "size",
+            Self::Eta => "eta",
+            Self::QueuePosition => "queue",
+            Self::AddedDate => "added",
+            Self::SeedingTime => "seeding",
+        }
+    }
+
+    pub(super) fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|field| field.name().eq_ignore_ascii_case(name))
+    }
+
+    fn next(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|field| *field == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    fn compare(self, left: &Torrent, right: &Torrent) -> Ordering {
+        match self {
+            Self::Name => natural_cmp(
+                left.name.as_deref().unwrap_or_default(),
+                right.name.as_deref().unwrap_or_default(),
+            ),
+            Self::Status => left.status.cmp(&right.status),
+            Self::Progress => float_cmp(left.percent_done, right.percent_done),
+            Self::DownloadSpeed => left.rate_download.cmp(&right.rate_download),
+            Self::UploadSpeed => left.rate_upload.cmp(&right.rate_upload),
+            Self::Ratio => float_cmp(left.upload_ratio, right.upload_ratio),
+            Self::Size => left.total_size.cmp(&right.total_size),
+            Self::Eta => eta_key(left).cmp(&eta_key(right)),
+            Self::QueuePosition => left.queue_position.cmp(&right.queue_position),
+            Self::AddedDate => left.added_date.cmp(&right.added_date),
+            Self::SeedingTime => left.seconds_seeding.cmp(&right.seconds_seeding),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(super) struct Sort {
+    field: SortField,
+    descending: bool,
+}
+
+impl Sort {
+    /// Parses a sort of the configuration, like `ratio desc`, the order being ascending by
+    /// default.
+    pub(super) fn parse(value: &str) -> Option<Self> {
+        let mut words = value.split_whitespace();
+        let field = SortField::from_name(words.next()?)?;
+        let descending = match words.next() {
+            None | Some("asc") => false,
+            Some("desc") => true,
+            Some(_) => return None,
+        };
+        words.next().is_none().then_some(Self { field, descending })
+    }
+
+    pub(super) fn new(field: SortField, descending: bool) -> Self {
+        Self { field, descending }
+    }
+
+    pub(super) fn descending(&self) -> bool {
+        self.descending
+    }
+
+    pub(super) fn with_next_field(self) -> Self {
+        Self {
+            field: self.field.next(),
+            descending: self.descending,
+        }
+    }
+
+    pub(super) fn with_toggled_direction(self) -> Self {
+        Self {
+            field: self.field,
+            descending: !self.descending,
+        }
+    }
+
+    pub(super) fn label(&self) -> String {
+        let arrow = if self.descending { "↓" } else { "↑" };
+        format!("{} {arrow}", self.field.label())
+    }
+
+    /// Compares on the sort field, then on the name and the id so that the order stays stable
+    /// between two refreshes.
+    pub(super) fn compare(&self, left: &Torrent, right: &Torrent) -> Ordering {
+        let primary = self.field.compare(left, right);
+        let primary = if self.descending {
+            primary.reverse()
+        } else {
+            primary
+        };
+        primary
+            .then_with(|| SortField::Name.compare(left, right))
+            .then_with(|| left.id.cmp(&right.id))
+    }
+}
+
+impl std::fmt::Display for Sort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let direction = if self.descending { "desc" } else { "asc" };
+        write!(f, "{} {direction}", self.field.name())
+    }
+}
+
+fn float_cmp(left: Option<f32>, right: Option<f32>) -> Ordering {
+    left.unwrap_or(-1.0).total_cmp(&right.unwrap_or(-1.0))
+}
+
+/// Unknown and unavailable ETAs are reported as negative values, they go after the known ones.
+fn eta_key(item: &Torrent) -> i64 {
+    match item.eta {
+        Some(value) if value >= 0 => value,
+        _ => i64::MAX,
+    }
+}
+
+/// Compares strings ignoring the case, and comparing the sequences of digits by their value, so
+/// that `episode 2` comes before `episode 10`.
+fn natural_cmp(left: &str, right: &str) -> Ordering {
+    let mut left = left.chars().peekable();
+    let mut right = right.chars().peekable();
+    loop {
+        match (left.peek().copied(), right.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(l), Some(r)) if l.is_ascii_digit() && r.is_ascii_digit() => {
+                let l = take_number(&mut left);
+                let r = take_number(&mut right);
+                let ordering = l.len().cmp(&r.len()).then_with(|| l.cmp(&r));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(l), Some(r)) => {
+                let ordering = l.to_lowercase().cmp(r.to_lowercase());
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                left.next();
+                right.next();
+            }
+        }
+    }
+}
+
+/// Consumes a sequence of digits, without the leading zeros.
+fn take_number(iter: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
+    let mut number = String::new();
+    while let Some(c) = iter.next_if(|c| c.is_ascii_digit()) {
+        if !(number.is_empty() && c == '0') {
+            number.push(c);
+        }
+    }
+    number
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::{Sort, SortField, natural_cmp};
+
+    #[test]
+    fn sort_is_parsed_back_from_its_display() {
+        for sort in [Sort::default(), Sort::default().with_toggled_direction()] {
+            assert_eq!(Sort::parse(&sort.to_string()), Some(sort));
+        }
+        let sort = Sort::parse("RATIO desc").unwrap();
+        assert_eq!(sort.field, SortField::Ratio);
+        assert!(sort.descending);
+        let sort = Sort::parse("size").unwrap();
+        assert_eq!(sort.field, SortField::Size);
+        assert!(!sort.descending);
+        for value in ["", "weight", "ratio down", "ratio desc asc"] {
+            assert_eq!(Sort::parse(value), None, "{value}");
+        }
+    }
+
+    #[test]
+    fn natural_cmp_compares_numbers_by_value() {
+        assert_eq!(natural_cmp("episode 2", "episode 10"), Ordering::Less);
+        assert_eq!(natural_cmp("episode 10", "episode 9"), Ordering::Greater);
+        assert_eq!(natural_cmp("v1.10", "v1.9"), Ordering::Greater);
+    }
+
+    #[test]
+    fn natural_cmp_ignores_leading_zeros() {
+        assert_eq!(natural_cmp("track 007", "track 7"), Ordering::Equal);
+        assert_eq!(natural_cmp("track 007", "track 8"), Ordering::Less);
+        assert_eq!(natural_cmp("0", "00"), Ordering::Equal);
+    }
+
+    #[test]
+    fn natural_cmp_ignores_the_case() {
+        assert_eq!(natural_cmp("Alpha", "alpha"), Ordering::Equal);
+        assert_eq!(natural_cmp("alpha", "Beta"), Ordering::Less);
+        assert_eq!(natural_cmp("Éclair", "éclair"), Ordering::Equal);
+    }
+
+    #[test]
+    fn natural_cmp_puts_prefixes_first() {
+        assert_eq!(natural_cmp("debian", "debian 12"), Ordering::Less);
+        assert_eq!(natural_cmp("", "a"), Ordering::Less);
+        assert_eq!(natural_cmp("", ""), Ordering::Equal);
+    }
+}
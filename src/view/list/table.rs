@@ -0,0 +1,166 @@
+use ratatui::layout::Constraint;
+use ratatui::prelude::{Buffer, Rect};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Cell, Row, Table, Widget};
+use transmission_rpc::types::{Torrent, TorrentStatus};
+
+use crate::components::{
+    SIZE_FORMATTER, SPEED_FORMATTER, format_eta, torrent_status_label, tracker_hosts,
+};
+use crate::config::{Column, ColumnConfig};
+use crate::view::list::TorrentItem;
+
+const fn header(column: Column) -> &'static str {
+    match column {
+        Column::Name => "Name",
+        Column::Status => "Status",
+        Column::Progress => "Progress",
+        Column::Size => "Size",
+        Column::DownloadSpeed => "Down",
+        Column::UploadSpeed => "Up",
+        Column::Eta => "ETA",
+        Column::Ratio => "Ratio",
+        Column::Peers => "Peers",
+        Column::Queue => "Queue",
+        Column::Label => "Label",
+        Column::Tracker => "Tracker",
+        Column::DownloadDir => "Directory",
+    }
+}
+
+const fn default_width(column: Column) -> u16 {
+    match column {
+        Column::Name => 0,
+        Column::Status => 18,
+        Column::Progress => 16,
+        Column::Size | Column::DownloadSpeed | Column::UploadSpeed => 10,
+        Column::Eta => 12,
+        Column::Ratio | Column::Peers | Column::Queue => 6,
+        Column::Label => 12,
+        Column::Tracker => 20,
+        Column::DownloadDir => 24,
+    }
+}
+
+fn width(config: &ColumnConfig) -> Option<u16> {
+    config.width.or(match config.column {
+        Column::Name => None,
+        other => Some(default_width(other)),
+    })
+}
+
+fn constraints(columns: &[ColumnConfig]) -> Vec<Constraint> {
+    columns
+        .iter()
+        .map(|config| match width(config) {
+            Some(width) => Constraint::Length(width),
+            None => Constraint::Fill(1),
+        })
+        .collect()
+}
+
+/// Draws a bar of the given width followed by the percentage.
+fn progress_bar(ratio: f64, width: u16) -> Line<'static> {
+    let bar_width = width.saturating_sub(5) as usize;
+    let filled = ((ratio.clamp(0.0, 1.0) * bar_width as f64).round()) as usize;
+    Line::from(vec![
+        Span::styled("━".repeat(filled), Style::new().green().bold()),
+        Span::styled("━".repeat(bar_width - filled), Style::new().dark_gray()),
+        Span::raw(format!("{:>4.0}%", ratio * 100.0)),
+    ])
+}
+
+fn cell<'a>(item: &'a TorrentItem, config: &ColumnConfig) -> Cell<'a> {
+    let torrent: &Torrent = &item.torrent;
+    match config.column {
+        Column::Name => Cell::from(Line::from(item.name())),
+        Column::Status => {
+            let status = torrent.status.unwrap_or(TorrentStatus::Stopped);
+            let cell = Cell::from(torrent_status_label(status));
+            match status {
+                TorrentStatus::Seeding => cell.fg(Color::LightGreen),
+                TorrentStatus::Stopped => cell.fg(Color::Gray),
+                _ => cell,
+            }
+        }
+        Column::Progress => Cell::from(progress_bar(
+            torrent.percent_done.unwrap_or(0.0) as f64,
+            width(config).unwrap_or_default(),
+        )),
+        Column::Size => Cell::from(
+            SIZE_FORMATTER
+                .format(torrent.total_size.unwrap_or(0) as f64)
+                .to_string(),
+        ),
+        Column::DownloadSpeed => Cell::from(
+            SPEED_FORMATTER
+                .format(torrent.rate_download.unwrap_or(0) as f64)
+                .to_string(),
+        ),
+        Column::UploadSpeed => Cell::from(
+            SPEED_FORMATTER
+                .format(torrent.rate_upload.unwrap_or(0) as f64)
+                .to_string(),
+        ),
+        Column::Eta => Cell::from(torrent.eta.map(format_eta).unwrap_or_default()),
+        Column::Ratio => Cell::from(
+            torrent
+                .upload_ratio
+                .filter(|value| *value >= 0.0)
+                .map(|value| format!("{value:.2}"))
+                .unwrap_or_default(),
+        ),
+        Column::Peers => Cell::from(torrent.peers_connected.unwrap_or(0).to_string()),
+        Column::Queue => Cell::from(
+            torrent
+                .queue_position
+                .map(|value| value.to_string())
+                .unwrap_or_default(),
+        ),
+        Column::Label => Cell::from(torrent.labels.as_deref().unwrap_or_default().join(", ")),
+        Column::Tracker => Cell::from(tracker_hosts(torrent).next().unwrap_or_default()),
+        Column::DownloadDir => Cell::from(torrent.download_dir.as_deref().unwrap_or_default()),
+    }
+}
+
+pub(super) struct TableHeader<'a>(pub(super) &'a [ColumnConfig]);
+
+impl Widget for &TableHeader<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        Table::default()
+            .widths(constraints(self.0))
+            .header(Row::new(self.0.iter().map(|config| header(config.column))).bold())
+            .render(area, buf);
+    }
+}
+
+/// One line row of the table layout, rendered through the list to share the selection and
+/// the scrolling with the cards layout.
+pub(super) struct TableRow<'a> {
+    pub(super) item: &'a TorrentItem,
+    pub(super) columns: &'a [ColumnConfig],
+}
+
+impl Widget for &TableRow<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let cells = self
+            .columns
+            .iter()
+            .map(|config| cell(self.item, config))
+            .collect::<Vec<_>>();
+        Table::new([Row::new(cells)], constraints(self.columns)).render(area, buf);
+    }
+}
+
+impl crate::components::list::ListItem for &TableRow<'_> {
+    fn height(&self) -> u16 {
+        1
+    }
+}
@@ -0,0 +1,272 @@
+use chrono::{DateTime, Local};
+use crossterm::clipboard::CopyToClipboard;
+use crossterm::event::{Event, KeyCode, KeyModifiers, MouseEventKind};
+use ratatui::layout::{Constraint, Flex, Layout};
+use ratatui::prelude::{Buffer, Rect};
+use ratatui::style::{Color, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::block::Title;
+use ratatui::widgets::{Block, Clear, Padding, StatefulWidget, Widget};
+
+use crate::Level;
+use crate::components::list::{List, ListState};
+
+/// Entries kept in the log, the oldest ones being dropped first.
+const MAX_ENTRIES: usize = 1000;
+
+/// Notification kept in the log.
+#[derive(Clone, Debug)]
+pub(super) struct LogEntry {
+    time: DateTime<Local>,
+    level: Level,
+    message: String,
+    /// Number of times the message has been repeated in a row.
+    count: usize,
+}
+
+impl LogEntry {
+    fn level_label(&self) -> &'static str {
+        match self.level {
+            Level::Info => "INFO",
+            Level::Error => "ERROR",
+        }
+    }
+
+    fn text(&self) -> String {
+        let mut text = format!(
+            "{} {:<5} {}",
+            self.time.format("%Y-%m-%d %H:%M:%S"),
+            self.level_label(),
+            self.message
+        );
+        if self.count > 1 {
+            text.push_str(&format!(" (x{})", self.count));
+        }
+        text
+    }
+}
+
+impl Widget for &LogEntry {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let level = match self.level {
+            Level::Info => self.level_label().green(),
+            Level::Error => self.level_label().red(),
+        };
+        let mut line = Line::from(vec![
+            format!("{} ", self.time.format("%H:%M:%S")).fg(Color::Gray),
+            level,
+            format!(" {}", self.message).into(),
+        ]);
+        if self.count > 1 {
+            line.push_span(format!(" (x{})", self.count).fg(Color::Gray));
+        }
+        line.render(area, buf);
+    }
+}
+
+impl crate::components::list::ListItem for &LogEntry {
+    fn height(&self) -> u16 {
+        1
+    }
+}
+
+/// History of the notifications, the repeated messages being merged.
+#[derive(Default)]
+pub(super) struct Log {
+    entries: Vec<LogEntry>,
+}
+
+impl Log {
+    /// Adds the message, or counts it again when it repeats the last one.
+    pub(super) fn push(&mut self, level: Level, message: String) {
+        let time = Local::now();
+        if let Some(last) = self.entries.last_mut()
+            && last.level == level
+            && last.message == message
+        {
+            last.count += 1;
+            last.time = time;
+            return;
+        }
+        self.entries.push(LogEntry {
+            time,
+            level,
+            message,
+            count: 1,
+        });
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+    }
+
+    pub(super) fn entries(&self) -> &[LogEntry] {
+        &self.entries
+    }
+}
+
+/// Which entries of the log are displayed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum LevelFilter {
+    #[default]
+    All,
+    Errors,
+}
+
+/// Overlay listing the log, the most recent entries first.
+pub(super) struct LogView {
+    entries: Vec<LogEntry>,
+    /// Entries matching the filters.
+    visible: Vec<LogEntry>,
+    filter: String,
+    level: LevelFilter,
+    state: ListState,
+    /// Feedback of the last copy.
+    status: Option<String>,
+}
+
+impl LogView {
+    pub(super) fn new(log: &Log) -> Self {
+        let mut view = Self {
+            entries: Vec::new(),
+            visible: Vec::new(),
+            filter: String::new(),
+            level: LevelFilter::default(),
+            state: ListState::default(),
+            status: None,
+        };
+        view.sync(log);
+        view
+    }
+
+    /// Takes the entries added to the log while the view is open.
+    pub(super) fn sync(&mut self, log: &Log) {
+        self.entries = log.entries().iter().rev().cloned().collect();
+        self.apply_filter();
+    }
+
+    fn apply_filter(&mut self) {
+        let filter = self.filter.to_lowercase();
+        let selected = self.state.selected();
+        self.visible = self
+            .entries
+            .iter()
+            .filter(|entry| self.level == LevelFilter::All || entry.level == Level::Error)
+            .filter(|entry| entry.message.to_lowercase().contains(&filter))
+            .cloned()
+            .collect();
+        match selected {
+            Some(index) if index < self.visible.len() => {}
+            _ => self.state.select_first(self.visible.len()),
+        }
+    }
+
+    /// Copies the entries to the clipboard of the terminal.
+    fn copy(&mut self, entries: &[&LogEntry]) {
+        if entries.is_empty() {
+            return;
+        }
+        let text = entries
+            .iter()
+            .map(|entry| entry.text())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let copied =
+            crossterm::execute!(std::io::stdout(), CopyToClipboard::to_clipboard_from(text));
+        self.status = Some(match (copied, entries.len()) {
+            (Err(err), _) => format!("Could not copy: {err}"),
+            (Ok(_), 1) => "Copied the entry".to_string(),
+            (Ok(_), count) => format!("Copied {count} entries"),
+        });
+    }
+
+    /// Handles the input, and returns whether the log should be closed.
+    pub(super) fn update(&mut self, event: &Event) -> bool {
+        let len = self.visible.len();
+        self.status = None;
+        match event {
+            Event::Key(key) if key.modifiers.contains(KeyModifiers::CONTROL) => match key.code {
+                KeyCode::Char('y') => {
+                    let visible = std::mem::take(&mut self.visible);
+                    let selected = self.state.selected().and_then(|index| visible.get(index));
+                    self.copy(&selected.into_iter().collect::<Vec<_>>());
+                    self.visible = visible;
+                }
+                KeyCode::Char('a') => {
+                    // oldest first, like in a log file
+                    let visible = std::mem::take(&mut self.visible);
+                    self.copy(&visible.iter().rev().collect::<Vec<_>>());
+                    self.visible = visible;
+                }
+                _ => {}
+            },
+            Event::Key(key) => match key.code {
+                KeyCode::Esc | KeyCode::Enter => return true,
+                KeyCode::Backspace if self.filter.is_empty() => return true,
+                KeyCode::Backspace => {
+                    self.filter.pop();
+                    self.apply_filter();
+                }
+                KeyCode::Tab => {
+                    self.level = match self.level {
+                        LevelFilter::All => LevelFilter::Errors,
+                        LevelFilter::Errors => LevelFilter::All,
+                    };
+                    self.apply_filter();
+                }
+                KeyCode::Char(c) => {
+                    self.filter.push(c);
+                    self.apply_filter();
+                }
+                KeyCode::Up => self.state.select_previous(len),
+                KeyCode::Down => self.state.select_next(len),
+                KeyCode::PageUp => self.state.select_previous_page(len),
+                KeyCode::PageDown => self.state.select_next_page(len),
+                KeyCode::Home => self.state.select_first(len),
+                KeyCode::End => self.state.select_last(len),
+                _ => {}
+            },
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::ScrollUp => self.state.select_previous(len),
+                MouseEventKind::ScrollDown => self.state.select_next(len),
+                _ => {}
+            },
+            _ => {}
+        }
+        false
+    }
+
+    pub(super) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let vertical = Layout::vertical([Constraint::Percentage(80)]).flex(Flex::Center);
+        let horizontal = Layout::horizontal([Constraint::Percentage(90)]).flex(Flex::Center);
+        let [area] = vertical.areas(area);
+        let [area] = horizontal.areas(area);
+
+        let footer = if let Some(status) = self.status.as_deref() {
+            Line::from(format!(" {status} "))
+        } else if self.filter.is_empty() {
+            Line::from(" Type to search - <Tab> Errors only - <C-y> Copy - <C-a> Copy all ")
+        } else {
+            Line::from(vec![" /".bold(), self.filter.as_str().into(), "█ ".into()])
+        };
+        let title = match self.level {
+            LevelFilter::All => " Log ",
+            LevelFilter::Errors => " Log: errors ",
+        };
+        let block = Block::bordered()
+            .title(Title::from(title.bold()))
+            .title_bottom(footer)
+            .padding(Padding::horizontal(1));
+        let inner = block.inner(area);
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+        if self.visible.is_empty() {
+            Line::from("Nothing to show".fg(Color::Gray)).render(inner, buf);
+        } else {
+            List::new(&self.visible).render(inner, buf, &mut self.state);
+        }
+    }
+}
@@ -1,65 +1,266 @@
 use ratatui::Frame;
+use ratatui::prelude::{Buffer, Rect};
+use ratatui::style::Stylize;
+use ratatui::text::Line;
 use ratatui::widgets::Widget;
 
+mod help;
+mod inspector;
 mod list;
+mod log;
+mod quit;
+mod toast;
 mod torrent;
 
 enum Route {
-    List(list::ListView),
-    Torrent(torrent::TorrentView),
+    List(Box<list::ListView>),
+    Torrent(Box<torrent::TorrentView>),
 }
 
 impl Default for Route {
     fn default() -> Self {
-        Self::List(list::ListView::default())
+        Self::List(Box::default())
     }
 }
 
-#[derive(Default)]
+impl Route {
+    fn init(&mut self, ctx: &crate::Context) {
+        match self {
+            Self::List(inner) => inner.init(ctx),
+            Self::Torrent(inner) => inner.init(ctx),
+        }
+    }
+
+    /// Called when the route gets visible again after the one above it has been popped.
+    fn resume(&mut self, ctx: &crate::Context) {
+        match self {
+            Self::List(inner) => inner.resume(ctx),
+            Self::Torrent(inner) => inner.init(ctx),
+        }
+    }
+
+    fn update(&mut self, event: crate::Event, ctx: &crate::Context) {
+        match self {
+            Self::List(inner) => inner.update(event, ctx),
+            Self::Torrent(inner) => inner.update(event, ctx),
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Self::List(_) => "Transmission".into(),
+            Self::Torrent(inner) => inner.label(),
+        }
+    }
+
+    fn render(&mut self, breadcrumb: &str, area: Rect, buf: &mut Buffer) {
+        match self {
+            Self::List(inner) => inner.render(breadcrumb, area, buf),
+            Self::Torrent(inner) => inner.render(breadcrumb, area, buf),
+        }
+    }
+}
+
+/// Modal displayed above the routes.
+enum Overlay {
+    Help(help::Help),
+    Inspector(inspector::Inspector),
+    Log(log::LogView),
+    QuitConfirm(quit::QuitConfirm),
+}
+
+impl Overlay {
+    /// Handles the input, and returns whether the overlay should be closed.
+    fn update(&mut self, event: &crossterm::event::Event, ctx: &crate::Context) -> bool {
+        match self {
+            Self::Help(inner) => inner.update(event),
+            Self::Inspector(inner) => inner.update(event, ctx.keymap()),
+            Self::Log(inner) => inner.update(event),
+            Self::QuitConfirm(inner) => inner.update(event, ctx),
+        }
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        match self {
+            Self::Help(inner) => inner.render(area, buf),
+            Self::Inspector(inner) => inner.render(area, buf),
+            Self::Log(inner) => inner.render(area, buf),
+            Self::QuitConfirm(inner) => inner.render(area, buf),
+        }
+    }
+}
+
+/// Stack of the opened routes, the last one being the visible one.
 pub(crate) struct View {
-    inner: Route,
+    stack: Vec<Route>,
+    /// Overlays above the routes, the last one receiving the input until it is closed.
+    overlays: Vec<Overlay>,
+    log: log::Log,
+    toasts: toast::Toasts,
+    calls: inspector::Calls,
+    /// Attempt of the reconnection to the daemon, while it can't be reached.
+    reconnecting: Option<u32>,
+}
+
+impl Default for View {
+    fn default() -> Self {
+        Self {
+            stack: vec![Route::default()],
+            overlays: Vec::new(),
+            log: log::Log::default(),
+            toasts: toast::Toasts::default(),
+            calls: inspector::Calls::default(),
+            reconnecting: None,
+        }
+    }
 }
 
 impl View {
-    pub fn draw(&self, frame: &mut Frame) {
+    pub fn draw(&mut self, frame: &mut Frame) {
         frame.render_widget(self, frame.area());
     }
 
-    pub(crate) fn init(&mut self, ctx: &crate::Context) {
-        match &mut self.inner {
-            Route::List(inner) => inner.init(ctx),
-            Route::Torrent(inner) => inner.init(ctx),
+    fn current(&mut self) -> &mut Route {
+        self.stack
+            .last_mut()
+            .expect("the navigation stack should never be empty")
+    }
+
+    fn push(&mut self, mut route: Route, ctx: &crate::Context) {
+        route.init(ctx);
+        self.stack.push(route);
+    }
+
+    fn pop(&mut self, ctx: &crate::Context) {
+        if self.stack.len() > 1 {
+            self.stack.pop();
+            self.current().resume(ctx);
+        }
+    }
+
+    fn breadcrumb(&self) -> String {
+        self.stack
+            .iter()
+            .map(Route::label)
+            .collect::<Vec<_>>()
+            .join(" > ")
+    }
+
+    /// Shows the message in a toast and keeps it in the log.
+    fn notify(&mut self, level: crate::Level, message: String) {
+        match level {
+            crate::Level::Info => tracing::info!(message),
+            crate::Level::Error => tracing::error!(message),
+        }
+        self.log.push(level, message.clone());
+        self.toasts.push(level, message);
+        for overlay in &mut self.overlays {
+            if let Overlay::Log(inner) = overlay {
+                inner.sync(&self.log);
+            }
         }
     }
 
+    pub(crate) fn init(&mut self, ctx: &crate::Context) {
+        self.current().init(ctx);
+    }
+
     pub(crate) fn update(&mut self, event: crate::Event, ctx: &crate::Context) {
+        let _span = tracing::debug_span!("update", event = event.name()).entered();
         match event {
-            crate::Event::OpenList => {
-                let mut view = list::ListView::default();
-                view.init(ctx);
-                self.inner = Route::List(view);
+            crate::Event::Back => self.pop(ctx),
+            crate::Event::Notify(level, message) => self.notify(level, message),
+            crate::Event::Tick => self.toasts.prune(),
+            crate::Event::Reconnecting(attempt) => self.reconnecting = Some(attempt),
+            crate::Event::Reconnected => {
+                self.reconnecting = None;
+                self.notify(crate::Level::Info, "Reconnected to the daemon".to_string());
+            }
+            // the failure itself is reported by the view of the refresh
+            crate::Event::ReconnectFailed => self.reconnecting = None,
+            crate::Event::RpcCall(call) => {
+                self.calls.push(*call);
+                for overlay in &mut self.overlays {
+                    if let Overlay::Inspector(inner) = overlay {
+                        inner.sync(&self.calls);
+                    }
+                }
+            }
+            crate::Event::OpenInspector(scope) => {
+                let inspector = inspector::Inspector::new(&self.calls, scope);
+                self.overlays.push(Overlay::Inspector(inspector));
             }
-            crate::Event::OpenTorrent(torrent_id) => {
-                let mut view = torrent::TorrentView::new(torrent_id);
-                view.init(ctx);
-                self.inner = Route::Torrent(view);
+            crate::Event::OpenLog => {
+                self.overlays
+                    .push(Overlay::Log(log::LogView::new(&self.log)));
             }
-            other => match &mut self.inner {
-                Route::List(inner) => inner.update(other, ctx),
-                Route::Torrent(inner) => inner.update(other, ctx),
+            crate::Event::OpenHelp(scope) => {
+                self.overlays
+                    .push(Overlay::Help(help::Help::new(ctx.keymap(), scope)));
+            }
+            crate::Event::Quit => match ctx.pending_actions() {
+                pending if pending > 0 && ctx.config().confirm_quit => {
+                    let confirm = quit::QuitConfirm::new(ctx, pending);
+                    self.overlays.push(Overlay::QuitConfirm(confirm));
+                }
+                _ => ctx.send_event(crate::Event::Exit),
             },
+            crate::Event::InputError(err) => {
+                self.notify(
+                    crate::Level::Error,
+                    format!("Could not read the input: {err}"),
+                );
+            }
+            crate::Event::InputEvent(event) if !self.overlays.is_empty() => {
+                if self
+                    .overlays
+                    .last_mut()
+                    .is_some_and(|overlay| overlay.update(&event, ctx))
+                {
+                    self.overlays.pop();
+                }
+            }
+            crate::Event::OpenTorrent(torrent_id, siblings) => {
+                let view = torrent::TorrentView::new(torrent_id, siblings);
+                self.push(Route::Torrent(Box::new(view)), ctx);
+            }
+            other => {
+                match &other {
+                    crate::Event::TorrentListUpdateError(err) => {
+                        let message = format!("Could not refresh the list: {}", err.explain());
+                        self.notify(crate::Level::Error, message);
+                    }
+                    crate::Event::TorrentUpdateError(_, err) => {
+                        let message = format!("Could not refresh the torrent: {}", err.explain());
+                        self.notify(crate::Level::Error, message);
+                    }
+                    _ => {}
+                }
+                self.current().update(other, ctx);
+            }
         }
     }
 }
 
-impl Widget for &View {
-    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer)
+impl Widget for &mut View {
+    fn render(self, area: Rect, buf: &mut Buffer)
     where
         Self: Sized,
     {
-        match &self.inner {
-            Route::List(inner) => inner.render(area, buf),
-            Route::Torrent(inner) => inner.render(area, buf),
+        let breadcrumb = self.breadcrumb();
+        self.current().render(&breadcrumb, area, buf);
+        for overlay in &mut self.overlays {
+            overlay.render(area, buf);
+        }
+        if let Some(attempt) = self.reconnecting {
+            Line::from(
+                format!(" Reconnecting (attempt {attempt}) ")
+                    .black()
+                    .on_yellow(),
+            )
+            .centered()
+            .render(Rect { height: 1, ..area }, buf);
         }
+        self.toasts.render(area, buf);
     }
 }
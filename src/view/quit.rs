@@ -0,0 +1,46 @@
+use crossterm::event::Event;
+use ratatui::prelude::{Buffer, Rect};
+use ratatui::widgets::Widget;
+
+use crate::components::dialog::Dialog;
+use crate::components::subtitle::Subtitle;
+use crate::keymap::{Command, Scope};
+
+const OPTIONS: [Command; 2] = [Command::Close, Command::Yes];
+
+/// Asks for a confirmation before quitting while actions are still running.
+pub(super) struct QuitConfirm {
+    dialog: Dialog,
+    area: Rect,
+}
+
+impl QuitConfirm {
+    pub(super) fn new(ctx: &crate::Context, pending: usize) -> Self {
+        let message = match pending {
+            1 => "An action is still running.\nQuit anyway?".to_string(),
+            count => format!("{count} actions are still running.\nQuit anyway?"),
+        };
+        let options = Subtitle::new(ctx.keymap(), Scope::Dialog, &OPTIONS);
+        Self {
+            dialog: Dialog::new("Quit", options).with_message(message),
+            area: Rect::default(),
+        }
+    }
+
+    /// Handles the input, and returns whether the dialog should be closed.
+    pub(super) fn update(&mut self, event: &Event, ctx: &crate::Context) -> bool {
+        match self.dialog.update(ctx.keymap(), self.area, event) {
+            Some(Command::Yes | Command::Select) => {
+                ctx.send_event(crate::Event::Exit);
+                true
+            }
+            Some(Command::Close | Command::No) => true,
+            _ => false,
+        }
+    }
+
+    pub(super) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        self.area = area;
+        self.dialog.render(area, buf);
+    }
+}
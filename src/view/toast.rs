@@ -0,0 +1,97 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use ratatui::prelude::{Buffer, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Block, Clear, Paragraph, Widget, Wrap};
+
+use crate::Level;
+
+const INFO_DURATION: Duration = Duration::from_secs(4);
+const ERROR_DURATION: Duration = Duration::from_secs(8);
+/// Toasts displayed at once, the oldest ones being dropped first.
+const MAX_TOASTS: usize = 4;
+const MAX_WIDTH: u16 = 60;
+
+struct Toast {
+    level: Level,
+    message: String,
+    expires: Instant,
+}
+
+impl Toast {
+    fn color(&self) -> Color {
+        match self.level {
+            Level::Info => Color::Green,
+            Level::Error => Color::Red,
+        }
+    }
+}
+
+/// Transient messages stacked in the bottom right corner.
+#[derive(Default)]
+pub(super) struct Toasts {
+    items: VecDeque<Toast>,
+}
+
+impl Toasts {
+    pub(super) fn push(&mut self, level: Level, message: String) {
+        let duration = match level {
+            Level::Info => INFO_DURATION,
+            Level::Error => ERROR_DURATION,
+        };
+        let expires = Instant::now() + duration;
+        // a repeated message only keeps its toast longer
+        if let Some(toast) = self
+            .items
+            .iter_mut()
+            .find(|toast| toast.level == level && toast.message == message)
+        {
+            toast.expires = expires;
+            return;
+        }
+        self.items.push_back(Toast {
+            level,
+            message,
+            expires,
+        });
+        while self.items.len() > MAX_TOASTS {
+            self.items.pop_front();
+        }
+    }
+
+    /// Removes the expired toasts.
+    pub(super) fn prune(&mut self) {
+        let now = Instant::now();
+        self.items.retain(|toast| toast.expires > now);
+    }
+}
+
+impl Widget for &Toasts {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let width = MAX_WIDTH.min(area.width.saturating_sub(2));
+        let mut bottom = area.bottom().saturating_sub(1);
+        // the most recent toast is the lowest one
+        for toast in self.items.iter().rev() {
+            let text_width = width.saturating_sub(2).max(1);
+            let lines = (toast.message.chars().count() as u16)
+                .div_ceil(text_width)
+                .clamp(1, 3);
+            let height = lines + 2;
+            if bottom < area.y + height {
+                break;
+            }
+            let x = area.right().saturating_sub(width + 1).max(area.x);
+            let toast_area = Rect::new(x, bottom - height, width, height);
+            Clear.render(toast_area, buf);
+            Paragraph::new(toast.message.as_str())
+                .wrap(Wrap { trim: true })
+                .block(Block::bordered().border_style(Style::new().fg(toast.color())))
+                .render(toast_area, buf);
+            bottom -= height;
+        }
+    }
+}
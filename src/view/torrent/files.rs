@@ -0,0 +1,59 @@
+use ratatui::layout::Constraint;
+use ratatui::prelude::{Buffer, Rect};
+use ratatui::style::Stylize;
+use ratatui::widgets::{Row, Table, Widget};
+use transmission_rpc::types::{Priority, Torrent};
+
+use crate::components::{SIZE_FORMATTER, format_percent};
+
+fn priority_label(priority: Priority) -> &'static str {
+    match priority {
+        Priority::Low => "Low",
+        Priority::Normal => "Normal",
+        Priority::High => "High",
+    }
+}
+
+pub(super) fn render(item: &Torrent, scroll: u16, area: Rect, buf: &mut Buffer) -> u16 {
+    let files = item.files.as_deref().unwrap_or_default();
+    let stats = item.file_stats.as_deref().unwrap_or_default();
+    // the header takes the first line
+    let max_scroll = super::max_scroll(files.len(), area.height.saturating_sub(1));
+    let rows = files
+        .iter()
+        .enumerate()
+        .skip(usize::from(scroll.min(max_scroll)))
+        .map(|(index, file)| {
+            let stat = stats.get(index);
+            let progress = if file.length > 0 {
+                file.bytes_completed as f64 / file.length as f64
+            } else {
+                1.0
+            };
+            Row::new(vec![
+                file.name.clone(),
+                SIZE_FORMATTER.format(file.length as f64).to_string(),
+                format_percent(progress),
+                stat.map(|stat| priority_label(stat.priority))
+                    .unwrap_or_default()
+                    .to_string(),
+                match stat.map(|stat| stat.wanted) {
+                    Some(false) => "Skip".to_string(),
+                    _ => "Get".to_string(),
+                },
+            ])
+        });
+    Table::new(
+        rows,
+        [
+            Constraint::Min(20),
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(6),
+        ],
+    )
+    .header(Row::new(["Name", "Size", "Done", "Priority", "Get"]).bold())
+    .render(area, buf);
+    max_scroll
+}
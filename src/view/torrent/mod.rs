@@ -0,0 +1,288 @@
+use crossterm::event::{Event, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Constraint, Layout, Position};
+use ratatui::prelude::{Buffer, Rect};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::block::Title;
+use ratatui::widgets::{Block, Padding, Tabs, Widget};
+use transmission_rpc::types::Torrent;
+
+use crate::Action;
+use crate::components::mouse::tab_at;
+use crate::components::subtitle::Subtitle;
+use crate::keymap::{Chord, Command, Resolution, Scope};
+
+mod files;
+mod overview;
+mod peers;
+mod trackers;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Tab {
+    #[default]
+    Overview,
+    Files,
+    Peers,
+    Trackers,
+}
+
+impl Tab {
+    const ALL: [Tab; 4] = [Tab::Overview, Tab::Files, Tab::Peers, Tab::Trackers];
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Overview => "Overview",
+            Self::Files => "Files",
+            Self::Peers => "Peers",
+            Self::Trackers => "Trackers",
+        }
+    }
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|tab| *tab == self).unwrap_or(0)
+    }
+
+    fn next(self) -> Self {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    fn previous(self) -> Self {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    /// Renders the tab from the given line, returning the greatest line it can start from.
+    fn render(self, item: &Torrent, scroll: u16, area: Rect, buf: &mut Buffer) -> u16 {
+        match self {
+            Self::Overview => overview::render(item, scroll, area, buf),
+            Self::Files => files::render(item, scroll, area, buf),
+            Self::Peers => peers::render(item, scroll, area, buf),
+            Self::Trackers => trackers::render(item, scroll, area, buf),
+        }
+    }
+}
+
+/// Greatest scroll of a content of the given number of lines shown in the given height.
+fn max_scroll(len: usize, height: u16) -> u16 {
+    u16::try_from(len)
+        .unwrap_or(u16::MAX)
+        .saturating_sub(height)
+}
+
+pub struct TorrentView {
+    id: i64,
+    /// Ids of the torrents in the order of the list, used to jump to the previous/next one.
+    siblings: Vec<i64>,
+    tab: Tab,
+    error: Option<String>,
+    loading: bool,
+    item: Option<Box<Torrent>>,
+    /// First line of the active tab shown.
+    scroll: u16,
+    /// Height of the active tab and its greatest scroll during the last render, to page through it.
+    viewport: (u16, u16),
+    /// Areas of the view and of its tabs during the last render, to handle the clicks.
+    area: Rect,
+    tabs_area: Rect,
+    chord: Chord,
+    //
+    subtitle: Subtitle,
+}
+
+const HINTS: [Command; 7] = [
+    Command::Quit,
+    Command::Help,
+    Command::Back,
+    Command::Reload,
+    Command::NextTab,
+    Command::PreviousTorrent,
+    Command::NextTorrent,
+];
+
+impl TorrentView {
+    pub(super) fn new(torrent_id: i64, siblings: Vec<i64>) -> Self {
+        Self {
+            id: torrent_id,
+            siblings,
+            tab: Tab::default(),
+            error: None,
+            loading: false,
+            item: None,
+            scroll: 0,
+            viewport: (0, 0),
+            area: Rect::default(),
+            tabs_area: Rect::default(),
+            chord: Chord::default(),
+            //
+            subtitle: Subtitle::default(),
+        }
+    }
+
+    pub(super) fn init(&mut self, context: &crate::Context) {
+        self.subtitle = Subtitle::new(context.keymap(), Scope::Detail, &HINTS);
+        context.send_action(Action::RefreshTorrent(self.id));
+    }
+
+    pub(super) fn id(&self) -> i64 {
+        self.id
+    }
+
+    /// Switches the view to another torrent, keeping the active tab.
+    pub(super) fn set_torrent(&mut self, id: i64, context: &crate::Context) {
+        self.id = id;
+        self.item = None;
+        self.error = None;
+        self.scroll = 0;
+        context.send_action(Action::RefreshTorrent(self.id));
+    }
+
+    fn handle_sibling(&mut self, forward: bool, context: &crate::Context) {
+        let Some(position) = self.siblings.iter().position(|id| *id == self.id) else {
+            return;
+        };
+        let next = if forward {
+            position.checked_add(1)
+        } else {
+            position.checked_sub(1)
+        };
+        if let Some(id) = next.and_then(|index| self.siblings.get(index)).copied() {
+            self.set_torrent(id, context);
+        }
+    }
+
+    fn tab_labels() -> Vec<String> {
+        Tab::ALL
+            .iter()
+            .enumerate()
+            .map(|(index, tab)| format!("{} {}", index + 1, tab.label()))
+            .collect()
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent, context: &crate::Context) {
+        match event.kind {
+            MouseEventKind::ScrollUp => return self.scroll_by(-1),
+            MouseEventKind::ScrollDown => return self.scroll_by(1),
+            MouseEventKind::Down(MouseButton::Left) => {}
+            _ => return,
+        }
+        let position = Position::new(event.column, event.row);
+        let labels = Self::tab_labels();
+        if let Some(index) = tab_at(labels.iter().map(String::as_str), self.tabs_area, position) {
+            self.set_tab(Tab::ALL[index]);
+        } else if let Some(command) = self.subtitle.command_at(self.area, position) {
+            self.run_command(command, context);
+        }
+    }
+
+    pub(super) fn run_command(&mut self, command: Command, context: &crate::Context) {
+        match command {
+            Command::Quit => context.send_event(crate::Event::Quit),
+            Command::Help => context.send_event(crate::Event::OpenHelp(Scope::Detail)),
+            Command::Log => context.send_event(crate::Event::OpenLog),
+            Command::Inspector => context.send_event(crate::Event::OpenInspector(Scope::Detail)),
+            Command::Reload => context.send_action(Action::RefreshTorrent(self.id)),
+            Command::Back => context.send_event(crate::Event::Back),
+            Command::NextTab => self.set_tab(self.tab.next()),
+            Command::PreviousTab => self.set_tab(self.tab.previous()),
+            Command::TabOverview => self.set_tab(Tab::Overview),
+            Command::TabFiles => self.set_tab(Tab::Files),
+            Command::TabPeers => self.set_tab(Tab::Peers),
+            Command::TabTrackers => self.set_tab(Tab::Trackers),
+            Command::Up => self.scroll_by(-1),
+            Command::Down => self.scroll_by(1),
+            Command::PageUp => self.scroll_by(-i32::from(self.viewport.0)),
+            Command::PageDown => self.scroll_by(i32::from(self.viewport.0)),
+            Command::PreviousTorrent => self.handle_sibling(false, context),
+            Command::NextTorrent => self.handle_sibling(true, context),
+            _ => {}
+        }
+    }
+
+    pub(super) fn update(&mut self, event: crate::Event, context: &crate::Context) {
+        match event {
+            crate::Event::InputEvent(Event::Mouse(inner)) => self.handle_mouse(inner, context),
+            crate::Event::InputEvent(Event::Key(inner)) => {
+                if let Resolution::Command(command) =
+                    self.chord.resolve(context.keymap(), Scope::Detail, inner)
+                {
+                    self.run_command(command, context);
+                }
+            }
+            crate::Event::TorrentUpdateStart => {
+                self.error = None;
+                self.loading = true;
+            }
+            // ignore the responses for a torrent we navigated away from
+            crate::Event::TorrentUpdate(item) if item.id != Some(self.id) => {}
+            crate::Event::TorrentUpdate(item) => {
+                self.error = None;
+                self.item = Some(item);
+                self.loading = false;
+            }
+            crate::Event::TorrentUpdateError(id, _) if id != self.id => {}
+            crate::Event::TorrentUpdateError(_, err) => {
+                self.error = Some(err.to_string());
+                self.loading = false;
+            }
+            _ => {}
+        }
+    }
+
+    fn set_tab(&mut self, tab: Tab) {
+        self.tab = tab;
+        self.scroll = 0;
+    }
+
+    fn scroll_by(&mut self, delta: i32) {
+        let max = i32::from(self.viewport.1);
+        self.scroll = (i32::from(self.scroll) + delta).clamp(0, max) as u16;
+    }
+
+    fn render_inner(&mut self, area: Rect, buf: &mut Buffer) {
+        let [tabs, _, content] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .areas(area);
+
+        self.tabs_area = tabs;
+        Tabs::new(Self::tab_labels().into_iter().map(Line::from))
+            .select(self.tab.index())
+            .highlight_style(Style::new().bold().reversed())
+            .render(tabs, buf);
+
+        if let Some(item) = self.item.as_deref() {
+            let max_scroll = self.tab.render(item, self.scroll, content, buf);
+            self.viewport = (content.height, max_scroll);
+        }
+    }
+}
+
+impl TorrentView {
+    pub(super) fn label(&self) -> String {
+        self.item
+            .as_ref()
+            .and_then(|item| item.name.clone())
+            .unwrap_or_else(|| "Torrent".into())
+    }
+
+    pub(super) fn render(&mut self, breadcrumb: &str, area: Rect, buf: &mut Buffer) {
+        self.area = area;
+        let name = format!(" {breadcrumb} > {} ", self.tab.label());
+        let title = if self.loading {
+            Title::from(format!("{name}(Loading...) "))
+        } else if let Some(err) = self.error.as_ref() {
+            Title::from(format!("{name}({err}) ").red())
+        } else {
+            Title::from(name)
+        };
+        let block = Block::bordered()
+            .title(title)
+            .title_bottom(self.subtitle.line())
+            .padding(Padding::horizontal(2));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        self.render_inner(inner, buf);
+    }
+}
@@ -0,0 +1,160 @@
+use ratatui::prelude::{Buffer, Rect};
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::{Paragraph, Widget};
+use transmission_rpc::types::{ErrorType, Torrent};
+
+use crate::components::{
+    SIZE_FORMATTER, format_date, format_duration, format_eta, format_percent, torrent_status_label,
+};
+
+pub(super) fn render(item: &Torrent, scroll: u16, area: Rect, buf: &mut Buffer) -> u16 {
+    let sections = sections(item);
+    let lines = sections
+        .iter()
+        .enumerate()
+        .flat_map(|(index, section)| section.lines(index > 0))
+        .collect::<Vec<_>>();
+    let max_scroll = super::max_scroll(lines.len(), area.height);
+    Paragraph::new(lines)
+        .scroll((scroll.min(max_scroll), 0))
+        .render(area, buf);
+    max_scroll
+}
+
+const LABEL_WIDTH: usize = 18;
+
+struct Section {
+    title: &'static str,
+    rows: Vec<(&'static str, String)>,
+}
+
+impl Section {
+    fn new(title: &'static str) -> Self {
+        Self {
+            title,
+            rows: Vec::new(),
+        }
+    }
+
+    fn row(mut self, label: &'static str, value: impl Into<String>) -> Self {
+        self.rows.push((label, value.into()));
+        self
+    }
+
+    fn lines(&self, with_spacing: bool) -> impl Iterator<Item = Line<'_>> {
+        let spacing = with_spacing.then(Line::default);
+        let header = Line::from(self.title.bold().underlined());
+        let rows = self.rows.iter().map(|(label, value)| {
+            Line::from(vec![
+                format!("{label:<LABEL_WIDTH$}").into(),
+                value.as_str().bold(),
+            ])
+        });
+        spacing
+            .into_iter()
+            .chain(std::iter::once(header))
+            .chain(rows)
+    }
+}
+
+fn format_size(value: Option<i64>) -> String {
+    SIZE_FORMATTER.format(value.unwrap_or(0) as f64).to_string()
+}
+
+/// Share of the pieces that are available, either locally or from the connected peers.
+fn availability(item: &Torrent) -> Option<f64> {
+    let pieces = item.availability.as_ref()?;
+    if pieces.is_empty() {
+        return None;
+    }
+    let available = pieces.iter().filter(|count| **count != 0).count();
+    Some(available as f64 / pieces.len() as f64)
+}
+
+fn sections(item: &Torrent) -> Vec<Section> {
+    let general = Section::new("General")
+        .row("Name", item.name.clone().unwrap_or_default())
+        .row("Hash", item.hash_string.clone().unwrap_or_default())
+        .row("Magnet link", item.magnet_link.clone().unwrap_or_default())
+        .row("Comment", item.comment.clone().unwrap_or_default())
+        .row("Creator", item.creator.clone().unwrap_or_default())
+        .row(
+            "Privacy",
+            match item.is_private {
+                Some(true) => "Private torrent",
+                Some(false) => "Public torrent",
+                None => "",
+            },
+        );
+
+    let mut transfer = Section::new("Transfer")
+        .row(
+            "Status",
+            item.status.map(torrent_status_label).unwrap_or_default(),
+        )
+        .row(
+            "Progress",
+            format_percent(item.percent_done.unwrap_or(0.0) as f64),
+        )
+        .row("Size", format_size(item.total_size))
+        .row(
+            "Downloaded",
+            format_size(item.downloaded_ever.map(|value| value as i64)),
+        )
+        .row("Uploaded", format_size(item.uploaded_ever))
+        .row(
+            "Corrupt",
+            format_size(item.corrupt_ever.map(|value| value as i64)),
+        )
+        .row(
+            "Ratio",
+            item.upload_ratio
+                .filter(|value| *value >= 0.0)
+                .map(|value| format!("{value:.2}"))
+                .unwrap_or_else(|| "None".into()),
+        )
+        .row(
+            "Availability",
+            availability(item).map(format_percent).unwrap_or_default(),
+        )
+        .row("ETA", item.eta.map(format_eta).unwrap_or_default())
+        .row(
+            "Seeding time",
+            item.seconds_seeding
+                .map(format_duration)
+                .unwrap_or_default(),
+        );
+    if let Some(error) = item
+        .error_string
+        .as_deref()
+        .filter(|_| !matches!(item.error, None | Some(ErrorType::Ok)))
+    {
+        transfer = transfer.row("Error", error);
+    }
+
+    let dates = Section::new("Dates")
+        .row("Created", format_date(item.date_created))
+        .row("Added", format_date(item.added_date))
+        .row("Started", format_date(item.start_date))
+        .row("Completed", format_date(item.done_date))
+        .row("Last activity", format_date(item.activity_date));
+
+    let location = Section::new("Location").row(
+        "Download directory",
+        item.download_dir.clone().unwrap_or_default(),
+    );
+
+    let webseeds = item.webseeds.as_deref().unwrap_or_default();
+    let webseeds = if webseeds.is_empty() {
+        Section::new("Web seeds").row("", "None")
+    } else {
+        webseeds
+            .iter()
+            .fold(Section::new("Web seeds"), |section, url| {
+                section.row("", url.as_str())
+            })
+    };
+
+    vec![general, transfer, dates, location, webseeds]
+}
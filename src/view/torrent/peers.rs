@@ -0,0 +1,42 @@
+use ratatui::layout::Constraint;
+use ratatui::prelude::{Buffer, Rect};
+use ratatui::style::Stylize;
+use ratatui::widgets::{Row, Table, Widget};
+use transmission_rpc::types::Torrent;
+
+use crate::components::{SPEED_FORMATTER, format_percent};
+
+pub(super) fn render(item: &Torrent, scroll: u16, area: Rect, buf: &mut Buffer) -> u16 {
+    let peers = item.peers.as_deref().unwrap_or_default();
+    // the header takes the first line
+    let max_scroll = super::max_scroll(peers.len(), area.height.saturating_sub(1));
+    let rows = peers
+        .iter()
+        .skip(usize::from(scroll.min(max_scroll)))
+        .map(|peer| {
+            Row::new(vec![
+                format!("{}:{}", peer.address, peer.port),
+                peer.client_name.clone(),
+                format_percent(peer.progress as f64),
+                SPEED_FORMATTER
+                    .format(peer.rate_to_client as f64)
+                    .to_string(),
+                SPEED_FORMATTER.format(peer.rate_to_peer as f64).to_string(),
+                peer.flag_str.clone(),
+            ])
+        });
+    Table::new(
+        rows,
+        [
+            Constraint::Length(28),
+            Constraint::Min(12),
+            Constraint::Length(8),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(8),
+        ],
+    )
+    .header(Row::new(["Address", "Client", "Done", "Down", "Up", "Flags"]).bold())
+    .render(area, buf);
+    max_scroll
+}
@@ -0,0 +1,69 @@
+use ratatui::layout::Constraint;
+use ratatui::prelude::{Buffer, Rect};
+use ratatui::style::Stylize;
+use ratatui::widgets::{Row, Table, Widget};
+use transmission_rpc::types::Torrent;
+
+use crate::components::format_date;
+
+/// Transmission reports `-1` for the counts it doesn't know about yet.
+fn format_count(value: i64) -> String {
+    if value < 0 {
+        "-".into()
+    } else {
+        value.to_string()
+    }
+}
+
+pub(super) fn render(item: &Torrent, scroll: u16, area: Rect, buf: &mut Buffer) -> u16 {
+    let trackers = item.tracker_stats.as_deref().unwrap_or_default();
+    // the header takes the first line
+    let max_scroll = super::max_scroll(trackers.len(), area.height.saturating_sub(1));
+    let rows = trackers
+        .iter()
+        .skip(usize::from(scroll.min(max_scroll)))
+        .map(|tracker| {
+            let last_result = if !tracker.has_announced {
+                "Not announced yet".to_string()
+            } else if tracker.last_announce_succeeded {
+                format!("{} peers", tracker.last_announce_peer_count)
+            } else {
+                tracker.last_announce_result.clone()
+            };
+            Row::new(vec![
+                tracker.tier.to_string(),
+                tracker.host.clone(),
+                last_result,
+                format_count(tracker.seeder_count),
+                format_count(tracker.leecher_count),
+                format_count(tracker.download_count),
+                format_date(Some(tracker.next_announce_time)),
+            ])
+        });
+    Table::new(
+        rows,
+        [
+            Constraint::Length(4),
+            Constraint::Min(20),
+            Constraint::Min(20),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(19),
+        ],
+    )
+    .header(
+        Row::new([
+            "Tier",
+            "Host",
+            "Last announce",
+            "Seeders",
+            "Leechers",
+            "Downloads",
+            "Next announce",
+        ])
+        .bold(),
+    )
+    .render(area, buf);
+    max_scroll
+}